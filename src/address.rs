@@ -0,0 +1,273 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+use tiny_keccak::{Hasher, Keccak};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HexError {
+    MissingPrefix,
+    InvalidLength { expected: usize, found: usize },
+    InvalidCharacter { index: usize, character: char },
+    InvalidChecksum { expected: String },
+}
+
+impl Display for HexError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            HexError::MissingPrefix => write!(f, "missing 0x prefix"),
+            HexError::InvalidLength { expected, found } => {
+                write!(f, "expected {expected} hex digits, found {found}")
+            }
+            HexError::InvalidCharacter { index, character } => {
+                write!(f, "invalid hex character {character:?} at {index}")
+            }
+            HexError::InvalidChecksum { expected } => {
+                write!(f, "invalid EIP-55 checksum, expected {expected}")
+            }
+        }
+    }
+}
+
+impl Error for HexError {}
+
+fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut out = [0_u8; 32];
+    hasher.update(input);
+    hasher.finalize(&mut out);
+    out
+}
+
+fn decode_hex<const N: usize>(input: &str) -> Result<[u8; N], HexError> {
+    let digits = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .ok_or(HexError::MissingPrefix)?;
+    let found = digits.chars().count();
+    if found != N * 2 {
+        return Err(HexError::InvalidLength {
+            expected: N * 2,
+            found,
+        });
+    }
+
+    let mut out = [0_u8; N];
+    for (index, character) in digits.chars().enumerate() {
+        let nibble = character.to_digit(16).ok_or(HexError::InvalidCharacter {
+            index: index + 2,
+            character,
+        })?;
+        // A digit is at most 15, so the narrowing is lossless
+        #[allow(clippy::cast_possible_truncation)]
+        let nibble = nibble as u8;
+        out[index / 2] |= if index % 2 == 0 { nibble << 4 } else { nibble };
+    }
+    Ok(out)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(2 + bytes.len() * 2);
+    out.push_str("0x");
+    for byte in bytes {
+        // Writing into a String can't fail
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
+}
+
+// 20-byte account or contract address; equality ignores the case it was written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Address([u8; 20]);
+
+impl Address {
+    #[must_use]
+    pub fn from_bytes(bytes: [u8; 20]) -> Self {
+        Address(bytes)
+    }
+
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    // EIP-55 mixed-case representation
+    #[must_use]
+    pub fn to_checksum(&self) -> String {
+        let lower = encode_hex(&self.0);
+        let hash = keccak256(&lower.as_bytes()[2..]);
+        let mut out = String::with_capacity(lower.len());
+        out.push_str("0x");
+        for (index, character) in lower[2..].chars().enumerate() {
+            let nibble = if index % 2 == 0 {
+                hash[index / 2] >> 4
+            } else {
+                hash[index / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                out.push(character.to_ascii_uppercase());
+            } else {
+                out.push(character);
+            }
+        }
+        out
+    }
+
+    /// Parses an address and, when it is written in mixed case, requires a valid
+    /// EIP-55 checksum.
+    ///
+    /// # Errors
+    /// Returns a `HexError` for a bad prefix, length, digit or checksum.
+    pub fn parse_checksummed(input: &str) -> Result<Self, HexError> {
+        let address = Address(decode_hex(input)?);
+        let digits = &input[2..];
+        let mixed_case = digits.chars().any(|c| c.is_ascii_lowercase())
+            && digits.chars().any(|c| c.is_ascii_uppercase());
+        if mixed_case {
+            let expected = address.to_checksum();
+            if expected[2..] != *digits {
+                return Err(HexError::InvalidChecksum { expected });
+            }
+        }
+        Ok(address)
+    }
+}
+
+impl FromStr for Address {
+    type Err = HexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Address::parse_checksummed(s)
+    }
+}
+
+impl Display for Address {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", encode_hex(&self.0))
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|err| de::Error::custom(format!("invalid address {s:?}: {err}")))
+    }
+}
+
+// 32-byte transaction hash
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TxHash([u8; 32]);
+
+impl TxHash {
+    #[must_use]
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        TxHash(bytes)
+    }
+
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl FromStr for TxHash {
+    type Err = HexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_hex(s).map(TxHash)
+    }
+}
+
+impl Display for TxHash {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", encode_hex(&self.0))
+    }
+}
+
+impl Serialize for TxHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TxHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|err| de::Error::custom(format!("invalid transaction hash {s:?}: {err}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_checksum_works() {
+        let checksummed = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for input in &checksummed {
+            let address: Address = input.parse().unwrap();
+            assert_eq!(address.to_checksum(), *input);
+            assert_eq!(address.to_string(), input.to_lowercase());
+        }
+
+        let lower: Address = checksummed[0].to_lowercase().parse().unwrap();
+        let upper: Address = format!("0x{}", checksummed[0][2..].to_uppercase())
+            .parse()
+            .unwrap();
+        assert_eq!(lower, upper);
+    }
+
+    #[test]
+    fn address_parse_errors() {
+        assert_eq!(
+            "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse::<Address>(),
+            Err(HexError::MissingPrefix)
+        );
+        assert_eq!(
+            "0x0".parse::<Address>(),
+            Err(HexError::InvalidLength {
+                expected: 40,
+                found: 1
+            })
+        );
+        assert_eq!(
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaeg".parse::<Address>(),
+            Err(HexError::InvalidCharacter {
+                index: 41,
+                character: 'g'
+            })
+        );
+        assert_eq!(
+            "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".parse::<Address>(),
+            Err(HexError::InvalidChecksum {
+                expected: "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn tx_hash_works() {
+        let input = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060";
+        let hash: TxHash = input.to_uppercase().replace("0X", "0x").parse().unwrap();
+        assert_eq!(hash.to_string(), input);
+        assert!("0x5c50".parse::<TxHash>().is_err());
+
+        let parsed: TxHash = serde_json::from_str(&format!("{input:?}")).unwrap();
+        assert_eq!(parsed, hash);
+        assert!(serde_json::from_str::<Address>(r#""0x12""#).is_err());
+    }
+}
@@ -0,0 +1,800 @@
+use primitive_types::U256;
+use serde::de::{Unexpected, Visitor};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+// Wei and friends carry 18 decimals
+pub const ETH_DECIMALS: u8 = 18;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AmountError {
+    Empty,
+    InvalidFormat(String),
+    Negative(String),
+    Fractional(String),
+    Overflow(String),
+    DecimalsOutOfRange(u64),
+}
+
+impl Display for AmountError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AmountError::Empty => write!(f, "empty amount"),
+            AmountError::InvalidFormat(value) => write!(f, "invalid amount {value:?}"),
+            AmountError::Negative(value) => write!(f, "negative amount {value:?}"),
+            AmountError::Fractional(value) => {
+                write!(f, "amount {value:?} has more precision than allowed")
+            }
+            AmountError::Overflow(value) => {
+                write!(f, "amount {value:?} does not fit in 256 bits")
+            }
+            AmountError::DecimalsOutOfRange(decimals) => {
+                write!(f, "{decimals} decimals do not fit in 256 bits")
+            }
+        }
+    }
+}
+
+impl Error for AmountError {}
+
+fn pow10(exponent: u32) -> Option<U256> {
+    U256::from(10).checked_pow(U256::from(exponent))
+}
+
+// Parses a plain or scientific decimal (`12`, `1.5`, `1.5e+21`) and returns its value
+// multiplied by 10^scale, failing rather than dropping any non-zero digit
+fn parse_scaled(input: &str, scale: u8) -> Result<U256, AmountError> {
+    scale_decimal(input, scale, false)
+}
+
+// Same, but digits past the scale are rounded half up instead of rejected; for
+// floats, which only approximate what the API meant anyway
+fn parse_rounded(input: &str, scale: u8) -> Result<U256, AmountError> {
+    scale_decimal(input, scale, true)
+}
+
+fn scale_decimal(input: &str, scale: u8, round: bool) -> Result<U256, AmountError> {
+    let invalid = || AmountError::InvalidFormat(input.to_string());
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(AmountError::Empty);
+    }
+    let unsigned = trimmed.strip_prefix('+').unwrap_or(trimmed);
+    if let Some(negated) = unsigned.strip_prefix('-') {
+        let is_zero = negated.chars().all(|c| matches!(c, '0' | '.'));
+        if !is_zero {
+            return Err(AmountError::Negative(input.to_string()));
+        }
+        return Ok(U256::zero());
+    }
+
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(idx) => {
+            let exponent = unsigned[idx + 1..].parse::<i64>().map_err(|_| invalid())?;
+            (&unsigned[..idx], exponent)
+        }
+        None => (unsigned, 0),
+    };
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(idx) => (&mantissa[..idx], &mantissa[idx + 1..]),
+        None => (mantissa, ""),
+    };
+    let all_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if integer.is_empty() && fraction.is_empty() || !all_digits(integer) || !all_digits(fraction) {
+        return Err(invalid());
+    }
+
+    let digits = format!("{integer}{fraction}")
+        .trim_start_matches('0')
+        .to_string();
+    if digits.is_empty() {
+        return Ok(U256::zero());
+    }
+
+    let overflow = || AmountError::Overflow(input.to_string());
+    let fraction_len = i64::try_from(fraction.len()).map_err(|_| invalid())?;
+    // An exponent past i64 either way can't describe a 256-bit amount
+    let shift = exponent
+        .checked_sub(fraction_len)
+        .and_then(|shift| shift.checked_add(i64::from(scale)))
+        .ok_or_else(|| if exponent > 0 { overflow() } else { invalid() })?;
+    if shift >= 0 {
+        let shift = u32::try_from(shift).map_err(|_| overflow())?;
+        let value = U256::from_dec_str(&digits).map_err(|_| overflow())?;
+        value
+            .checked_mul(pow10(shift).ok_or_else(overflow)?)
+            .ok_or_else(overflow)
+    } else {
+        let cut = shift
+            .checked_neg()
+            .and_then(|cut| usize::try_from(cut).ok())
+            .ok_or_else(invalid)?;
+        let keep = digits.len().saturating_sub(cut);
+        let kept = if keep == 0 {
+            U256::zero()
+        } else {
+            U256::from_dec_str(&digits[..keep]).map_err(|_| overflow())?
+        };
+        let dropped = &digits[keep..];
+        if dropped.chars().all(|c| c == '0') {
+            return Ok(kept);
+        }
+        if !round {
+            return Err(AmountError::Fractional(input.to_string()));
+        }
+        // When the cut reaches past the digits, the first one dropped is an implied zero
+        if cut <= digits.len() && dropped.as_bytes()[0] >= b'5' {
+            return kept.checked_add(U256::one()).ok_or_else(overflow);
+        }
+        Ok(kept)
+    }
+}
+
+// From 2^53 on an f64 no longer holds every integer, so its digits may have been rounded
+const MAX_EXACT_F64: f64 = 9_007_199_254_740_992.0;
+
+// JSON numbers reach us as f64 unless they are plain integers; LowerExp yields the
+// shortest digits that round-trip, i.e. the digits the API actually wrote
+fn f64_digits(value: f64) -> String {
+    format!("{value:e}")
+}
+
+/// Unsigned 256-bit integer amount in a token's smallest unit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawAmount(pub U256);
+
+impl RawAmount {
+    #[must_use]
+    pub fn zero() -> Self {
+        RawAmount(U256::zero())
+    }
+
+    #[must_use]
+    pub fn value(&self) -> U256 {
+        self.0
+    }
+
+    #[must_use]
+    pub fn checked_add(self, other: RawAmount) -> Option<RawAmount> {
+        self.0.checked_add(other.0).map(RawAmount)
+    }
+
+    #[must_use]
+    pub fn checked_sub(self, other: RawAmount) -> Option<RawAmount> {
+        self.0.checked_sub(other.0).map(RawAmount)
+    }
+}
+
+impl From<u64> for RawAmount {
+    fn from(value: u64) -> Self {
+        RawAmount(U256::from(value))
+    }
+}
+
+impl From<U256> for RawAmount {
+    fn from(value: U256) -> Self {
+        RawAmount(value)
+    }
+}
+
+impl FromStr for RawAmount {
+    type Err = AmountError;
+
+    // Accepts decimal, scientific and 0x-prefixed hex notation
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if let Some(hex) = trimmed
+            .strip_prefix("0x")
+            .or_else(|| trimmed.strip_prefix("0X"))
+        {
+            return U256::from_str_radix(hex, 16)
+                .map(RawAmount)
+                .map_err(|_| AmountError::InvalidFormat(s.to_string()));
+        }
+        parse_scaled(s, 0).map(RawAmount)
+    }
+}
+
+impl Display for RawAmount {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+struct AmountVisitor {
+    scale: u8,
+}
+
+impl AmountVisitor {
+    fn parse<E: de::Error>(&self, value: &str) -> Result<U256, E> {
+        if self.scale == 0 {
+            return value
+                .parse::<RawAmount>()
+                .map(|raw| raw.0)
+                .map_err(de::Error::custom);
+        }
+        parse_scaled(value, self.scale).map_err(de::Error::custom)
+    }
+}
+
+impl Visitor<'_> for AmountVisitor {
+    type Value = U256;
+
+    fn expecting(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        fmtr.write_str("a non-negative number or numeric string")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<U256, E>
+    where
+        E: de::Error,
+    {
+        self.parse(&v.to_string())
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<U256, E>
+    where
+        E: de::Error,
+    {
+        if v < 0 {
+            return Err(de::Error::invalid_value(Unexpected::Signed(v), &self));
+        }
+        self.parse(&v.to_string())
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<U256, E>
+    where
+        E: de::Error,
+    {
+        if !v.is_finite() {
+            return Err(de::Error::invalid_value(Unexpected::Float(v), &self));
+        }
+        // Ethplorer sends balances such as `1.0e+26` as floats, and integers past u64
+        // arrive as floats too; both are read from their shortest digits
+        parse_rounded(&f64_digits(v), self.scale).map_err(de::Error::custom)
+    }
+
+    // Ethplorer sends `""` for amounts it has no figure for
+    fn visit_str<E>(self, v: &str) -> Result<U256, E>
+    where
+        E: de::Error,
+    {
+        if v.trim().is_empty() {
+            return Ok(U256::zero());
+        }
+        self.parse(v)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_any(AmountVisitor { scale: 0 })
+            .map(RawAmount)
+    }
+}
+
+// Written as a decimal string, JSON numbers can't hold 256 bits exactly
+impl Serialize for RawAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A raw amount together with the decimals needed to display it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenAmount {
+    raw: RawAmount,
+    decimals: u8,
+}
+
+impl TokenAmount {
+    /// # Errors
+    /// Fails when 10^decimals does not fit in 256 bits.
+    pub fn new(raw: RawAmount, decimals: u8) -> Result<Self, AmountError> {
+        pow10(u32::from(decimals))
+            .ok_or_else(|| AmountError::DecimalsOutOfRange(u64::from(decimals)))?;
+        Ok(TokenAmount { raw, decimals })
+    }
+
+    /// Parses a human readable decimal such as `"1.25"` without rounding.
+    ///
+    /// # Errors
+    /// Fails on malformed input, on more fractional digits than `decimals` allows and
+    /// on overflow.
+    pub fn from_decimal_str(value: &str, decimals: u8) -> Result<Self, AmountError> {
+        let raw = parse_scaled(value, decimals)?;
+        TokenAmount::new(RawAmount(raw), decimals)
+    }
+
+    #[must_use]
+    pub fn raw(&self) -> RawAmount {
+        self.raw
+    }
+
+    #[must_use]
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    // Changes the number of decimals, refusing to drop non-zero digits
+    #[must_use]
+    pub fn rescale(&self, decimals: u8) -> Option<TokenAmount> {
+        let raw = match decimals.cmp(&self.decimals) {
+            Ordering::Equal => self.raw.0,
+            Ordering::Greater => self
+                .raw
+                .0
+                .checked_mul(pow10(u32::from(decimals - self.decimals))?)?,
+            Ordering::Less => {
+                let (quotient, remainder) = self
+                    .raw
+                    .0
+                    .div_mod(pow10(u32::from(self.decimals - decimals))?);
+                if !remainder.is_zero() {
+                    return None;
+                }
+                quotient
+            }
+        };
+        TokenAmount::new(RawAmount(raw), decimals).ok()
+    }
+
+    #[must_use]
+    pub fn checked_add(&self, other: &TokenAmount) -> Option<TokenAmount> {
+        let decimals = self.decimals.max(other.decimals);
+        let (lhs, rhs) = (self.rescale(decimals)?, other.rescale(decimals)?);
+        Some(TokenAmount {
+            raw: lhs.raw.checked_add(rhs.raw)?,
+            decimals,
+        })
+    }
+
+    #[must_use]
+    pub fn checked_sub(&self, other: &TokenAmount) -> Option<TokenAmount> {
+        let decimals = self.decimals.max(other.decimals);
+        let (lhs, rhs) = (self.rescale(decimals)?, other.rescale(decimals)?);
+        Some(TokenAmount {
+            raw: lhs.raw.checked_sub(rhs.raw)?,
+            decimals,
+        })
+    }
+
+    #[must_use]
+    pub fn checked_mul(&self, factor: u64) -> Option<TokenAmount> {
+        Some(TokenAmount {
+            raw: RawAmount(self.raw.0.checked_mul(U256::from(factor))?),
+            decimals: self.decimals,
+        })
+    }
+
+    // Lossy conversion for charts and rough comparisons
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+}
+
+impl PartialEq for TokenAmount {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TokenAmount {}
+
+impl PartialOrd for TokenAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Compares by value, so 1.50 with two decimals equals 1.5 with one
+impl Ord for TokenAmount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let decimals = self.decimals.max(other.decimals);
+        let scale = |amount: &TokenAmount| {
+            let factor = pow10(u32::from(decimals - amount.decimals)).unwrap_or_default();
+            amount.raw.0.full_mul(factor)
+        };
+        scale(self).cmp(&scale(other))
+    }
+}
+
+impl Display for TokenAmount {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let digits = self.raw.0.to_string();
+        let decimals = usize::from(self.decimals);
+        if decimals == 0 {
+            return write!(f, "{digits}");
+        }
+        let padded = format!("{:0>width$}", digits, width = decimals + 1);
+        let (integer, fraction) = padded.split_at(padded.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            write!(f, "{integer}")
+        } else {
+            write!(f, "{integer}.{fraction}")
+        }
+    }
+}
+
+// Decimal ether values such as `ETH.balance`, kept exactly at 18 decimals
+pub(crate) mod eth_amount {
+    use super::{
+        f64_digits, parse_scaled, AmountVisitor, RawAmount, TokenAmount, ETH_DECIMALS,
+        MAX_EXACT_F64,
+    };
+    use serde::{Deserializer, Serializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<TokenAmount, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = deserializer.deserialize_any(AmountVisitor {
+            scale: ETH_DECIMALS,
+        })?;
+        Ok(TokenAmount {
+            raw: RawAmount(raw),
+            decimals: ETH_DECIMALS,
+        })
+    }
+
+    // Written as a number when an f64 reads back to the same amount, otherwise as a
+    // decimal string, so amounts with more digits than an f64 holds aren't rounded
+    pub fn serialize<S: Serializer>(
+        amount: &TokenAmount,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let float = amount.to_f64();
+        let exact = float.abs() < MAX_EXACT_F64
+            && parse_scaled(&f64_digits(float), ETH_DECIMALS).ok() == Some(amount.raw.0);
+        if exact {
+            serializer.serialize_f64(float)
+        } else {
+            serializer.collect_str(amount)
+        }
+    }
+}
+
+// Plain numeric fields, which Ethplorer sends as JSON numbers for some tokens and
+// as strings (`"18"`, `"1.5e3"`, `""`) for others
+pub(crate) trait Flexible: Sized + Default {
+    // Named in errors, e.g. "an unsigned integer"
+    const EXPECTING: &'static str;
+
+    fn from_u64(v: u64) -> Option<Self>;
+    fn from_i64(v: i64) -> Option<Self>;
+    fn from_f64(v: f64) -> Option<Self>;
+    fn parse(s: &str) -> Option<Self>;
+}
+
+impl Flexible for u64 {
+    const EXPECTING: &'static str = "an unsigned integer";
+
+    fn from_u64(v: u64) -> Option<Self> {
+        Some(v)
+    }
+
+    fn from_i64(v: i64) -> Option<Self> {
+        u64::try_from(v).ok()
+    }
+
+    fn from_f64(v: f64) -> Option<Self> {
+        if v.is_finite() && v.abs() < MAX_EXACT_F64 {
+            Self::parse(&f64_digits(v))
+        } else {
+            None
+        }
+    }
+
+    // Scientific notation is fine as long as it lands on a whole number
+    fn parse(s: &str) -> Option<Self> {
+        let value = parse_scaled(s, 0).ok()?;
+        if value > U256::from(u64::MAX) {
+            return None;
+        }
+        Some(value.as_u64())
+    }
+}
+
+impl Flexible for f64 {
+    const EXPECTING: &'static str = "a finite number";
+
+    #[allow(clippy::cast_precision_loss)]
+    fn from_u64(v: u64) -> Option<Self> {
+        Some(v as f64)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn from_i64(v: i64) -> Option<Self> {
+        Some(v as f64)
+    }
+
+    fn from_f64(v: f64) -> Option<Self> {
+        Some(v)
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        s.parse::<f64>().ok().filter(|v| v.is_finite())
+    }
+}
+
+struct FlexibleVisitor<T>(PhantomData<T>);
+
+impl<T: Flexible> Visitor<'_> for FlexibleVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmtr, "{} as a number or numeric string", T::EXPECTING)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        T::from_u64(v).ok_or_else(|| de::Error::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        T::from_i64(v).ok_or_else(|| de::Error::invalid_value(Unexpected::Signed(v), &self))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        T::from_f64(v).ok_or_else(|| de::Error::invalid_value(Unexpected::Float(v), &self))
+    }
+
+    // An empty string stands for a figure the API doesn't have
+    fn visit_str<E>(self, v: &str) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        let trimmed = v.trim();
+        if trimmed.is_empty() {
+            return Ok(T::default());
+        }
+        T::parse(trimmed).ok_or_else(|| de::Error::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+pub(crate) mod flexible {
+    use super::{Flexible, FlexibleVisitor};
+    use serde::{Deserialize, Deserializer};
+    use std::marker::PhantomData;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Flexible,
+    {
+        deserializer.deserialize_any(FlexibleVisitor(PhantomData))
+    }
+
+    // For fields that may also be `null`
+    pub fn deserialize_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Flexible,
+    {
+        #[derive(Deserialize)]
+        #[serde(bound = "T: Flexible")]
+        struct Present<T>(#[serde(deserialize_with = "deserialize")] T);
+
+        Option::<Present<T>>::deserialize(deserializer).map(|value| value.map(|present| present.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_amount_parse_works() {
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(max.parse::<RawAmount>().unwrap(), RawAmount(U256::MAX));
+        assert_eq!(
+            "1.5e+21".parse::<RawAmount>().unwrap().to_string(),
+            "1500000000000000000000"
+        );
+        assert_eq!("0x10".parse::<RawAmount>().unwrap(), RawAmount::from(16));
+        assert_eq!(
+            "1.5".parse::<RawAmount>(),
+            Err(AmountError::Fractional("1.5".to_string()))
+        );
+        assert_eq!(
+            "-1".parse::<RawAmount>(),
+            Err(AmountError::Negative("-1".to_string()))
+        );
+        assert_eq!(
+            "12a".parse::<RawAmount>(),
+            Err(AmountError::InvalidFormat("12a".to_string()))
+        );
+        assert!(format!("{max}0").parse::<RawAmount>().is_err());
+    }
+
+    #[test]
+    fn raw_amount_parse_rejects_extreme_exponents() {
+        assert_eq!(
+            "1e-9223372036854775808".parse::<RawAmount>(),
+            Err(AmountError::InvalidFormat(
+                "1e-9223372036854775808".to_string()
+            ))
+        );
+        assert_eq!(
+            "1.5e-9223372036854775808".parse::<RawAmount>(),
+            Err(AmountError::InvalidFormat(
+                "1.5e-9223372036854775808".to_string()
+            ))
+        );
+        assert_eq!(
+            "1e9223372036854775807".parse::<RawAmount>(),
+            Err(AmountError::Overflow("1e9223372036854775807".to_string()))
+        );
+        assert_eq!(
+            "0e-9223372036854775808".parse::<RawAmount>(),
+            Ok(RawAmount::zero())
+        );
+        assert!(parse_scaled("1e9223372036854775807", ETH_DECIMALS).is_err());
+        assert!(numbers(r#"{"count": "1e-9223372036854775808", "rate": 1}"#).is_err());
+    }
+
+    #[test]
+    fn raw_amount_deserialize_works() {
+        let parsed: Vec<RawAmount> =
+            serde_json::from_str(r#"[12, 1.5e3, "340282366920938463463374607431768211456"]"#)
+                .unwrap();
+        assert_eq!(parsed[0], RawAmount::from(12));
+        assert_eq!(parsed[1], RawAmount::from(1500));
+        assert_eq!(
+            parsed[2].to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert!(serde_json::from_str::<RawAmount>("-5").is_err());
+        assert!(serde_json::from_str::<RawAmount>("true").is_err());
+
+        // Large JSON numbers are floats by the time they arrive, so only strings are
+        // exact past 2^53
+        assert_eq!(
+            serde_json::from_str::<RawAmount>("1.0e+26")
+                .unwrap()
+                .to_string(),
+            "100000000000000000000000000"
+        );
+        assert_eq!(
+            serde_json::from_str::<RawAmount>("123456789012345678901")
+                .unwrap()
+                .to_string(),
+            "123456789012345680000"
+        );
+        assert_eq!(
+            serde_json::from_str::<RawAmount>("2.5").unwrap(),
+            RawAmount::from(3)
+        );
+        assert!(serde_json::from_str::<RawAmount>("9007199254740991").is_ok());
+        assert_eq!(
+            serde_json::from_str::<RawAmount>(r#""""#).unwrap(),
+            RawAmount::zero()
+        );
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Ether {
+        #[serde(with = "eth_amount")]
+        balance: TokenAmount,
+    }
+
+    fn ether(json: &str) -> String {
+        serde_json::from_str::<Ether>(json)
+            .unwrap()
+            .balance
+            .to_string()
+    }
+
+    #[test]
+    fn eth_amount_rounds_floats_to_wei() {
+        assert_eq!(ether(r#"{"balance": 1.5}"#), "1.5");
+        assert_eq!(
+            ether(r#"{"balance": 1.2345678901234567e-5}"#),
+            "0.000012345678901235"
+        );
+        assert_eq!(
+            ether(r#"{"balance": 0.00012345678901234501}"#),
+            "0.000123456789012345"
+        );
+        assert_eq!(ether(r#"{"balance": 4e-19}"#), "0");
+        assert_eq!(ether(r#"{"balance": 5e-19}"#), "0.000000000000000001");
+        // Strings are what the API wrote, so extra digits there are still an error
+        assert!(serde_json::from_str::<Ether>(r#"{"balance": "1e-19"}"#).is_err());
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Numbers {
+        #[serde(deserialize_with = "flexible::deserialize")]
+        count: u64,
+        #[serde(deserialize_with = "flexible::deserialize")]
+        rate: f64,
+        #[serde(deserialize_with = "flexible::deserialize_option", default)]
+        block: Option<u64>,
+    }
+
+    fn numbers(json: &str) -> Result<Numbers, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    #[test]
+    fn flexible_accepts_numbers_and_strings() {
+        let expected = Numbers {
+            count: 1500,
+            rate: 0.25,
+            block: Some(18),
+        };
+        assert_eq!(
+            numbers(r#"{"count": 1500, "rate": 0.25, "block": 18}"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            numbers(r#"{"count": "1500", "rate": "0.25", "block": "18"}"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            numbers(r#"{"count": "1.5e3", "rate": "2.5e-1", "block": 1.8e1}"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            numbers(r#"{"count": "", "rate": " ", "block": null}"#).unwrap(),
+            Numbers {
+                count: 0,
+                rate: 0.0,
+                block: None,
+            }
+        );
+        assert_eq!(numbers(r#"{"count": 1, "rate": 1}"#).unwrap().block, None);
+    }
+
+    #[test]
+    fn flexible_reports_bad_values() {
+        let err = |json| numbers(json).unwrap_err().to_string();
+        assert!(err(r#"{"count": "12abc", "rate": 1}"#).starts_with(
+            r#"invalid value: string "12abc", expected an unsigned integer as a number or numeric string"#
+        ));
+        assert!(err(r#"{"count": 1.5, "rate": 1}"#).contains("floating point `1.5`"));
+        assert!(err(r#"{"count": -1, "rate": 1}"#).contains("integer `-1`"));
+        assert!(err(r#"{"count": "1e20", "rate": 1}"#).contains(r#"string "1e20""#));
+        assert!(err(r#"{"count": 1, "rate": "NaN"}"#).contains("expected a finite number"));
+        assert!(err(r#"{"count": true, "rate": 1}"#).contains("boolean `true`"));
+    }
+
+    #[test]
+    fn token_amount_works() {
+        let amount = TokenAmount::new(RawAmount::from(1_234_500), 6).unwrap();
+        assert_eq!(amount.to_string(), "1.2345");
+        assert_eq!(TokenAmount::from_decimal_str("1.2345", 6).unwrap(), amount);
+        assert!(TokenAmount::from_decimal_str("1.2345678", 6).is_err());
+
+        let other = TokenAmount::from_decimal_str("0.0000000001", 18).unwrap();
+        let sum = amount.checked_add(&other).unwrap();
+        assert_eq!(sum.to_string(), "1.2345000001");
+        assert_eq!(sum.decimals(), 18);
+        assert_eq!(sum.checked_sub(&other).unwrap(), amount);
+        assert!(other.checked_sub(&amount).is_none());
+        assert_eq!(amount.checked_mul(2).unwrap().to_string(), "2.469");
+        assert_eq!(amount.rescale(4).unwrap().raw(), RawAmount::from(12345));
+        assert!(amount.rescale(3).is_none());
+        assert!(amount > other);
+        assert_eq!(
+            TokenAmount::new(RawAmount::zero(), 78),
+            Err(AmountError::DecimalsOutOfRange(78))
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use crate::address::Address;
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+use crate::client::Client;
+use crate::endpoint::{Endpoint, GetAddressInfo, GetTokenInfo};
+use crate::error::EthplorerError;
+#[cfg(feature = "async")]
+use crate::transport::AsyncTransport;
+use crate::transport::Transport;
+use crate::types::{AddressInfo, GetAddressInfoParams, TokenInfo};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+
+impl<T: Transport + Sync> Client<T> {
+    /// Calls every endpoint from at most `concurrency` worker threads (0 counts as 1),
+    /// returning one result per endpoint in input order, so a bad item fails on its own.
+    ///
+    /// Each worker goes through `call`, so every attempt waits on the client's rate
+    /// limiter and a retry sleeps in its worker, holding that slot until it's done.
+    /// `concurrency` therefore caps the requests in flight, while the rate limiter
+    /// alone decides how fast they go out; a higher `concurrency` than the plan's
+    /// quota allows only leaves more workers waiting on the limiter.
+    pub fn call_batch<E, I>(
+        &self,
+        endpoints: I,
+        concurrency: usize,
+    ) -> Vec<Result<E::Response, EthplorerError>>
+    where
+        E: Endpoint + Send,
+        E::Response: Send,
+        I: IntoIterator<Item = E>,
+    {
+        let endpoints: Vec<E> = endpoints.into_iter().collect();
+        let count = endpoints.len();
+        let queue = Mutex::new(endpoints.into_iter().enumerate());
+        let results = Mutex::new((0..count).map(|_| None).collect::<Vec<_>>());
+        let workers = concurrency.clamp(1, count.max(1));
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let next = queue.lock().unwrap_or_else(PoisonError::into_inner).next();
+                    let Some((index, endpoint)) = next else {
+                        break;
+                    };
+                    let result = self.call(endpoint);
+                    results.lock().unwrap_or_else(PoisonError::into_inner)[index] = Some(result);
+                });
+            }
+        });
+
+        results
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// `getTokenInfo` for each of `addresses`, through `call_batch`.
+    pub fn token_infos<I: IntoIterator<Item = Address>>(
+        &self,
+        addresses: I,
+        concurrency: usize,
+    ) -> Vec<Result<TokenInfo, EthplorerError>> {
+        let endpoints = addresses
+            .into_iter()
+            .map(|address| GetTokenInfo { address });
+        self.call_batch(endpoints, concurrency)
+    }
+
+    /// `getAddressInfo` for each of `addresses` with the same `params`, through
+    /// `call_batch`.
+    pub fn address_infos<I: IntoIterator<Item = Address>>(
+        &self,
+        addresses: I,
+        params: &GetAddressInfoParams,
+        concurrency: usize,
+    ) -> Vec<Result<AddressInfo, EthplorerError>> {
+        let endpoints = addresses.into_iter().map(|address| GetAddressInfo {
+            address,
+            params: params.clone(),
+        });
+        self.call_batch(endpoints, concurrency)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncTransport> AsyncClient<T> {
+    /// Async counterpart of `Client::call_batch`: at most `concurrency` calls (0 counts
+    /// as 1) are polled at once, each waiting on the rate limiter and its own retries.
+    pub async fn call_batch<E, I>(
+        &self,
+        endpoints: I,
+        concurrency: usize,
+    ) -> Vec<Result<E::Response, EthplorerError>>
+    where
+        E: Endpoint,
+        I: IntoIterator<Item = E>,
+    {
+        use futures::StreamExt;
+
+        futures::stream::iter(endpoints)
+            .map(|endpoint| self.call(endpoint))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Async counterpart of `Client::token_infos`.
+    pub async fn token_infos<I: IntoIterator<Item = Address>>(
+        &self,
+        addresses: I,
+        concurrency: usize,
+    ) -> Vec<Result<TokenInfo, EthplorerError>> {
+        let endpoints = addresses
+            .into_iter()
+            .map(|address| GetTokenInfo { address });
+        self.call_batch(endpoints, concurrency).await
+    }
+
+    /// Async counterpart of `Client::address_infos`.
+    pub async fn address_infos<I: IntoIterator<Item = Address>>(
+        &self,
+        addresses: I,
+        params: &GetAddressInfoParams,
+        concurrency: usize,
+    ) -> Vec<Result<AddressInfo, EthplorerError>> {
+        let endpoints = addresses.into_iter().map(|address| GetAddressInfo {
+            address,
+            params: params.clone(),
+        });
+        self.call_batch(endpoints, concurrency).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::{HttpResponse, MemoryTransport, TransportError};
+    use crate::types::RequestConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    const ADDRESSES: [&str; 3] = [
+        "0xdac17f958d2ee523a2206206994597c13d831ec7",
+        "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+        "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359",
+    ];
+
+    fn transport() -> MemoryTransport {
+        let mut transport = MemoryTransport::new();
+        for (index, address) in ADDRESSES.iter().enumerate().take(2) {
+            transport = transport.with_response(
+                &format!("https://api.ethplorer.io/getTokenInfo/{address}"),
+                HttpResponse::new(
+                    200,
+                    format!(
+                        r#"{{"address":"{address}","name":"T","symbol":"T","decimals":"{index}"}}"#
+                    ),
+                ),
+            );
+        }
+        transport.with_response(
+            &format!("https://api.ethplorer.io/getTokenInfo/{}", ADDRESSES[2]),
+            HttpResponse::new(
+                400,
+                r#"{"error":{"code":150,"message":"Address is not a token contract"}}"#,
+            ),
+        )
+    }
+
+    // Holds every request for a while, recording the most it saw at once
+    #[derive(Debug, Default)]
+    struct Gauge {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    impl Gauge {
+        fn enter(&self) {
+            let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max.fetch_max(current, Ordering::SeqCst);
+        }
+
+        fn leave(&self) -> HttpResponse {
+            self.current.fetch_sub(1, Ordering::SeqCst);
+            let body = r#"{"address":"0x0000000000000000000000000000000000000000","name":"T","symbol":"T","decimals":"0"}"#;
+            HttpResponse::new(200, body)
+        }
+    }
+
+    impl Transport for Gauge {
+        fn send(&self, _: &RequestConfig) -> Result<HttpResponse, TransportError> {
+            self.enter();
+            thread::sleep(Duration::from_millis(20));
+            Ok(self.leave())
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[async_trait::async_trait]
+    impl AsyncTransport for Gauge {
+        async fn send(&self, _: &RequestConfig) -> Result<HttpResponse, TransportError> {
+            self.enter();
+            futures_timer::Delay::new(Duration::from_millis(20)).await;
+            Ok(self.leave())
+        }
+    }
+
+    fn addresses() -> Vec<Address> {
+        let mut addresses: Vec<Address> = ADDRESSES.iter().map(|a| a.parse().unwrap()).collect();
+        addresses.insert(0, addresses[2]);
+        addresses
+    }
+
+    fn check(results: &[Result<TokenInfo, EthplorerError>]) {
+        assert_eq!(results.len(), 4);
+        assert!(matches!(results[0], Err(EthplorerError::Api(_))));
+        assert_eq!(
+            results[1].as_ref().unwrap().address.to_string(),
+            ADDRESSES[0]
+        );
+        assert_eq!(results[2].as_ref().unwrap().decimals, 1);
+        assert!(matches!(results[3], Err(EthplorerError::Api(_))));
+    }
+
+    #[test]
+    fn client_token_infos_keeps_input_order() {
+        let client = Client::new(transport());
+        check(&client.token_infos(addresses(), 3));
+        check(&client.token_infos(addresses(), 0));
+        assert_eq!(client.transport().requests().len(), 8);
+        assert!(client.token_infos(vec![], 4).is_empty());
+    }
+
+    #[test]
+    fn client_call_batch_bounds_concurrency() {
+        let client = Client::new(Gauge::default());
+        let results = client.token_infos(vec![Address::default(); 12], 3);
+        assert!(results.iter().all(Result::is_ok));
+        // Thread scheduling may keep the workers from all overlapping, never more
+        let max = client.transport().max.load(Ordering::SeqCst);
+        assert!((2..=3).contains(&max), "{} in flight at once", max);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_client_call_batch_bounds_concurrency() {
+        let client = AsyncClient::new(Gauge::default());
+        let results =
+            futures::executor::block_on(client.token_infos(vec![Address::default(); 12], 3));
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(client.transport().max.load(Ordering::SeqCst), 3);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_client_token_infos_keeps_input_order() {
+        let client = AsyncClient::new(transport());
+        check(&futures::executor::block_on(
+            client.token_infos(addresses(), 2),
+        ));
+    }
+}
@@ -0,0 +1,400 @@
+use crate::consts::{
+    GET_LAST_BLOCK_ROUTE, GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE, GET_TOKEN_INFO_ROUTE,
+    GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE, GET_TOP_ROUTE, GET_TOP_TOKENS_ROUTE,
+};
+use crate::types::RequestConfig;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Storage for raw response bodies keyed by `RequestConfig::fingerprint`.
+pub trait Cache: Send + Sync {
+    // A body stored under `key` that has not expired yet
+    fn get(&self, key: &str) -> Option<Vec<u8>>;
+
+    fn put(&self, key: &str, body: &[u8], ttl: Duration);
+
+    fn remove(&self, key: &str);
+
+    fn clear(&self);
+}
+
+struct MemoryEntry {
+    body: Vec<u8>,
+    expires: Instant,
+    used: u64,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    entries: HashMap<String, MemoryEntry>,
+    clock: u64,
+}
+
+/// In-memory cache evicting the least recently used entry once `capacity` is reached.
+pub struct MemoryCache {
+    capacity: usize,
+    state: Mutex<MemoryState>,
+}
+
+impl MemoryCache {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        MemoryCache {
+            capacity: capacity.max(1),
+            state: Mutex::new(MemoryState::default()),
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entries
+            .len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for MemoryCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MemoryCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.clock += 1;
+        let clock = state.clock;
+        let expired = match state.entries.get_mut(key) {
+            Some(entry) if entry.expires > Instant::now() => {
+                entry.used = clock;
+                return Some(entry.body.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            state.entries.remove(key);
+        }
+        None
+    }
+
+    fn put(&self, key: &str, body: &[u8], ttl: Duration) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.clock += 1;
+        let now = Instant::now();
+        if !state.entries.contains_key(key) && state.entries.len() >= self.capacity {
+            state.entries.retain(|_, entry| entry.expires > now);
+        }
+        if !state.entries.contains_key(key) && state.entries.len() >= self.capacity {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+        let entry = MemoryEntry {
+            body: body.to_vec(),
+            expires: now + ttl,
+            used: state.clock,
+        };
+        state.entries.insert(key.to_string(), entry);
+    }
+
+    fn remove(&self, key: &str) {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entries
+            .remove(key);
+    }
+
+    fn clear(&self) {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entries
+            .clear();
+    }
+}
+
+// FNV-1a, only used to turn a fingerprint into a file name
+fn fnv1a(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// Milliseconds, so that sub-second TTLs don't round down to nothing
+fn unix_now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, millis)
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// On-disk cache keeping one file per entry under `dir`, so entries survive restarts.
+/// Each file holds the expiry time in Unix milliseconds and the full key on their own
+/// lines, then the body.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+impl DiskCache {
+    /// # Errors
+    /// Fails when `dir` cannot be created.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(DiskCache { dir })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.cache", fnv1a(key)))
+    }
+
+    fn read(&self, key: &str) -> Option<(u64, Vec<u8>)> {
+        let contents = fs::read(self.path(key)).ok()?;
+        let mut parts = contents.splitn(3, |byte| *byte == b'\n');
+        let expires = std::str::from_utf8(parts.next()?).ok()?.parse().ok()?;
+        if parts.next()? != key.as_bytes() {
+            return None;
+        }
+        Some((expires, parts.next()?.to_vec()))
+    }
+}
+
+// Cache writes are best effort; a failed write only means a later miss
+impl Cache for DiskCache {
+    fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let (expires, body) = self.read(key)?;
+        if expires <= unix_now_millis() {
+            self.remove(key);
+            return None;
+        }
+        Some(body)
+    }
+
+    fn put(&self, key: &str, body: &[u8], ttl: Duration) {
+        let expires = unix_now_millis().saturating_add(millis(ttl));
+        let mut contents = format!("{expires}\n{key}\n").into_bytes();
+        contents.extend_from_slice(body);
+        let _ = fs::write(self.path(key), contents);
+    }
+
+    fn remove(&self, key: &str) {
+        if self.read(key).is_some() {
+            let _ = fs::remove_file(self.path(key));
+        }
+    }
+
+    fn clear(&self) {
+        if let Ok(entries) = fs::read_dir(&self.dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().is_some_and(|ext| ext == "cache") {
+                    let _ = fs::remove_file(path);
+                }
+            }
+        }
+    }
+}
+
+/// A cache backend together with the TTL of each route. Routes without a TTL are
+/// never cached. Clones share the backend.
+#[derive(Clone)]
+pub struct ResponseCache {
+    backend: Arc<dyn Cache>,
+    ttls: HashMap<String, Duration>,
+}
+
+impl ResponseCache {
+    // Starts from the default TTLs: seconds for the last block, minutes for token
+    // info and top lists, hours for grouped histories
+    #[must_use]
+    pub fn new(backend: impl Cache + 'static) -> Self {
+        let mut ttls = HashMap::new();
+        ttls.insert(GET_LAST_BLOCK_ROUTE.to_string(), Duration::from_secs(5));
+        for route in &[GET_TOKEN_INFO_ROUTE, GET_TOP_TOKENS_ROUTE, GET_TOP_ROUTE] {
+            ttls.insert((*route).to_string(), Duration::from_secs(5 * 60));
+        }
+        for route in &[
+            GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE,
+            GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE,
+        ] {
+            ttls.insert((*route).to_string(), Duration::from_secs(60 * 60));
+        }
+        ResponseCache {
+            backend: Arc::new(backend),
+            ttls,
+        }
+    }
+
+    #[must_use]
+    pub fn with_ttl(mut self, route: &str, ttl: Duration) -> Self {
+        self.ttls.insert(route.to_string(), ttl);
+        self
+    }
+
+    #[must_use]
+    pub fn without_ttl(mut self, route: &str) -> Self {
+        self.ttls.remove(route);
+        self
+    }
+
+    #[must_use]
+    pub fn ttl_for(&self, route: &str) -> Option<Duration> {
+        self.ttls.get(route).copied()
+    }
+
+    pub fn invalidate(&self, request: &RequestConfig) {
+        self.backend.remove(&request.fingerprint());
+    }
+
+    pub fn clear(&self) {
+        self.backend.clear();
+    }
+
+    fn ttl(&self, request: &RequestConfig) -> Option<Duration> {
+        if !request.method.is_idempotent() {
+            return None;
+        }
+        self.ttl_for(request.routes.first()?)
+    }
+
+    pub(crate) fn lookup(&self, request: &RequestConfig) -> Option<Vec<u8>> {
+        self.ttl(request)?;
+        self.backend.get(&request.fingerprint())
+    }
+
+    pub(crate) fn store(&self, request: &RequestConfig, body: &[u8]) {
+        if let Some(ttl) = self.ttl(request) {
+            self.backend.put(&request.fingerprint(), body, ttl);
+        }
+    }
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttls", &self.ttls)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ClientConfig;
+    use crate::{get_address_info_config, get_last_block_config, GetAddressInfoParams};
+
+    #[test]
+    fn fingerprint_ignores_api_key_and_param_order() {
+        let address = "0xdac17f958d2ee523a2206206994597c13d831ec7"
+            .parse()
+            .unwrap();
+        let params = GetAddressInfoParams {
+            token: Some(address),
+            show_eth_totals: true,
+            show_txs_count: false,
+            extra_params: vec![],
+        };
+        let mut first =
+            get_address_info_config(&ClientConfig::new().with_api_key("a"), &address, &params);
+        let second =
+            get_address_info_config(&ClientConfig::new().with_api_key("b"), &address, &params);
+        first.params.reverse();
+        assert_eq!(first.fingerprint(), second.fingerprint());
+        assert_eq!(
+            second.fingerprint(),
+            format!(
+                "GET https://api.ethplorer.io/getAddressInfo/{address}?showETHTotals=true&token={address}"
+            )
+        );
+    }
+
+    #[test]
+    fn memory_cache_evicts_least_recently_used() {
+        let cache = MemoryCache::new(2);
+        let ttl = Duration::from_secs(60);
+        cache.put("a", b"1", ttl);
+        cache.put("b", b"2", ttl);
+        assert_eq!(cache.get("a"), Some(b"1".to_vec()));
+        cache.put("c", b"3", ttl);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some(b"1".to_vec()));
+
+        cache.put("d", b"4", Duration::from_secs(0));
+        assert_eq!(cache.get("d"), None);
+        cache.remove("a");
+        assert_eq!(cache.get("a"), None);
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn disk_cache_works() {
+        let dir = std::env::temp_dir().join(format!("ethplorer-cache-{}", std::process::id()));
+        let cache = DiskCache::new(&dir).unwrap();
+        cache.put("GET key", b"{\n}", Duration::from_secs(60));
+        assert_eq!(cache.get("GET key"), Some(b"{\n}".to_vec()));
+        assert_eq!(
+            DiskCache::new(&dir).unwrap().get("GET key"),
+            Some(b"{\n}".to_vec())
+        );
+        cache.put("GET stale", b"{}", Duration::from_secs(0));
+        assert_eq!(cache.get("GET stale"), None);
+        cache.put("GET brief", b"{}", Duration::from_millis(500));
+        assert_eq!(cache.get("GET brief"), Some(b"{}".to_vec()));
+        std::thread::sleep(Duration::from_millis(600));
+        assert_eq!(cache.get("GET brief"), None);
+        cache.remove("GET key");
+        assert_eq!(cache.get("GET key"), None);
+        cache.put("GET key", b"{}", Duration::from_secs(60));
+        cache.clear();
+        assert_eq!(cache.get("GET key"), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn response_cache_uses_route_ttls() {
+        let cache = ResponseCache::new(MemoryCache::new(10));
+        assert_eq!(
+            cache.ttl_for(GET_LAST_BLOCK_ROUTE),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(cache.ttl_for("getAddressInfo"), None);
+
+        let request = get_last_block_config(&ClientConfig::default());
+        cache.store(&request, b"{}");
+        assert_eq!(cache.lookup(&request), Some(b"{}".to_vec()));
+        cache.invalidate(&request);
+        assert_eq!(cache.lookup(&request), None);
+
+        let cache = cache.without_ttl(GET_LAST_BLOCK_ROUTE);
+        cache.store(&request, b"{}");
+        assert_eq!(cache.lookup(&request), None);
+    }
+}
@@ -0,0 +1,849 @@
+use crate::cache::ResponseCache;
+use crate::config::{ClientConfig, LimitPolicy, LimitViolation};
+use crate::endpoint::Endpoint;
+use crate::error::EthplorerError;
+use crate::rate_limit::RateLimiter;
+use crate::retry::{gave_up, retry_after, with_attempts, RetryPolicy};
+use crate::strictness::{scoped, Parsed};
+#[cfg(feature = "async")]
+use crate::transport::ReqwestTransport;
+#[cfg(feature = "blocking")]
+use crate::transport::UreqTransport;
+#[cfg(feature = "async")]
+use crate::transport::{AsyncTransport, TransportError};
+use crate::transport::{HttpResponse, Transport};
+use crate::types::{ApiResponse, RequestConfig};
+#[cfg(feature = "async")]
+use futures::channel::oneshot;
+use serde::de::DeserializeOwned;
+#[cfg(feature = "async")]
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "async")]
+use std::collections::HashMap;
+#[cfg(feature = "async")]
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "async")]
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+
+// The error envelope wins over the status code since it carries the API's own reason
+fn parse_response<R: DeserializeOwned>(
+    response: &HttpResponse,
+    config: &ClientConfig,
+) -> Result<Parsed<R>, EthplorerError> {
+    let (parsed, warnings) = scoped(config.strictness, || {
+        if config.unknown_fields {
+            ApiResponse::<R>::from_slice_keeping_unknown(&response.body)
+        } else {
+            ApiResponse::<R>::from_slice(&response.body)
+        }
+    });
+    let body = || String::from_utf8_lossy(&response.body).into_owned();
+    match parsed {
+        Ok(ApiResponse::Error(err)) => Err(EthplorerError::Api(err)),
+        _ if !response.is_success() => Err(EthplorerError::Status {
+            status: response.status,
+            body: body(),
+        }),
+        Ok(ApiResponse::Ok(value)) => Ok(Parsed {
+            value,
+            warnings,
+            limit_violations: vec![],
+        }),
+        Err(source) => Err(EthplorerError::Deserialize {
+            source,
+            body: body(),
+        }),
+    }
+}
+
+// The violations to hand back with the response, which only `LimitPolicy::Warn` has
+fn check_limits(
+    config: &ClientConfig,
+    request: &RequestConfig,
+) -> Result<Vec<LimitViolation>, EthplorerError> {
+    match config.limit_policy {
+        LimitPolicy::Clamp => Ok(vec![]),
+        LimitPolicy::Warn => Ok(config.check_limits(request)),
+        LimitPolicy::Reject => {
+            let violations = config.check_limits(request);
+            if violations.is_empty() {
+                Ok(violations)
+            } else {
+                Err(EthplorerError::InvalidParams(violations))
+            }
+        }
+    }
+}
+
+fn from_cache<R: DeserializeOwned>(
+    cache: Option<&ResponseCache>,
+    request: &RequestConfig,
+    config: &ClientConfig,
+) -> Option<Parsed<R>> {
+    let body = cache?.lookup(request)?;
+    parse_response(&HttpResponse::new(200, body), config).ok()
+}
+
+// Only bodies that parsed into `R` are cached
+fn receive<R: DeserializeOwned>(
+    cache: Option<&ResponseCache>,
+    request: &RequestConfig,
+    response: &HttpResponse,
+    config: &ClientConfig,
+) -> Result<Parsed<R>, EthplorerError> {
+    let result = parse_response(response, config);
+    if let (Ok(_), Some(cache)) = (&result, cache) {
+        cache.store(request, &response.body);
+    }
+    result
+}
+
+/// Executes `RequestConfig`s over a blocking `Transport`.
+#[derive(Debug, Default)]
+pub struct Client<T> {
+    transport: T,
+    config: ClientConfig,
+    limiter: Option<RateLimiter>,
+    retry: Option<RetryPolicy>,
+    cache: Option<ResponseCache>,
+}
+
+impl<T: Transport> Client<T> {
+    #[must_use]
+    pub fn new(transport: T) -> Self {
+        Client {
+            transport,
+            config: ClientConfig::default(),
+            limiter: None,
+            retry: None,
+            cache: None,
+        }
+    }
+
+    // The transport keeps the timeouts it was built with, see `from_config`
+    #[must_use]
+    pub fn with_config(mut self, config: ClientConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    // Paces every request; pass a clone to share the limit with other clients
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    // Re-sends GET requests that fail for a transient reason
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    // Serves repeated GET requests from `cache` while their route TTL lasts
+    #[must_use]
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    #[must_use]
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    #[must_use]
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// # Errors
+    /// Fails when the transport fails or the body does not deserialize into `R`.
+    pub fn execute<R: DeserializeOwned>(
+        &self,
+        config: &RequestConfig,
+    ) -> Result<R, EthplorerError> {
+        self.execute_parsed(config).map(Parsed::into_inner)
+    }
+
+    /// Like `execute`, keeping the warnings of a lenient parse.
+    ///
+    /// # Errors
+    /// Fails when the transport fails or the body does not deserialize into `R`.
+    pub fn execute_parsed<R: DeserializeOwned>(
+        &self,
+        config: &RequestConfig,
+    ) -> Result<Parsed<R>, EthplorerError> {
+        let violations = check_limits(&self.config, config)?;
+        if let Some(value) = from_cache::<R>(self.cache.as_ref(), config, &self.config) {
+            return Ok(value.with_limit_violations(violations));
+        }
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            if let Some(limiter) = &self.limiter {
+                limiter.acquire();
+            }
+            let (result, hint) = match self.transport.send(config) {
+                Ok(response) => (
+                    receive::<R>(self.cache.as_ref(), config, &response, &self.config),
+                    retry_after(&response),
+                ),
+                Err(err) => (Err(err.into()), None),
+            };
+            let err = match result {
+                Ok(value) => return Ok(value.with_limit_violations(violations)),
+                Err(err) => err,
+            };
+            let delay = match &self.retry {
+                Some(retry) if retry.should_retry(config.method.is_idempotent(), attempt, &err) => {
+                    retry.delay(attempt, hint)
+                }
+                _ => return Err(with_attempts(err, attempt)),
+            };
+            let Some(delay) = delay else {
+                return Err(gave_up(err, attempt, hint));
+            };
+            thread::sleep(delay);
+        }
+    }
+
+    /// # Errors
+    /// Fails when the transport fails or the body does not deserialize into the
+    /// endpoint's response type.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn call<E: Endpoint>(&self, endpoint: E) -> Result<E::Response, EthplorerError> {
+        self.execute(&endpoint.to_config(&self.config))
+    }
+
+    /// # Errors
+    /// Same as `call`.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn call_parsed<E: Endpoint>(
+        &self,
+        endpoint: E,
+    ) -> Result<Parsed<E::Response>, EthplorerError> {
+        self.execute_parsed(&endpoint.to_config(&self.config))
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Client<UreqTransport> {
+    // Builds the transport from `config` as well, so its timeouts take effect
+    #[must_use]
+    pub fn from_config(config: ClientConfig) -> Self {
+        Client::new(UreqTransport::from_config(&config)).with_config(config)
+    }
+}
+
+#[cfg(feature = "async")]
+type Waiters = HashMap<String, Vec<oneshot::Sender<Result<HttpResponse, TransportError>>>>;
+
+// Callers waiting on an identical request that is already in flight, by `coalescing_key`
+#[cfg(feature = "async")]
+#[derive(Debug, Default)]
+struct InFlight {
+    waiters: Mutex<Waiters>,
+}
+
+#[cfg(feature = "async")]
+impl InFlight {
+    // `None` makes the caller the leader that sends the request
+    fn join(&self, key: &str) -> Option<oneshot::Receiver<Result<HttpResponse, TransportError>>> {
+        let mut waiters = self.waiters.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(waiting) = waiters.get_mut(key) {
+            let (sender, receiver) = oneshot::channel();
+            waiting.push(sender);
+            return Some(receiver);
+        }
+        waiters.insert(key.to_string(), vec![]);
+        None
+    }
+
+    fn take(&self, key: &str) -> Vec<oneshot::Sender<Result<HttpResponse, TransportError>>> {
+        self.waiters
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(key)
+            .unwrap_or_default()
+    }
+}
+
+// Clears the leader's entry if its future is dropped before finishing, so waiters
+// see a closed channel instead of hanging
+#[cfg(feature = "async")]
+struct Leader<'a> {
+    in_flight: &'a InFlight,
+    key: Option<String>,
+}
+
+#[cfg(feature = "async")]
+impl Leader<'_> {
+    fn finish(mut self, result: &Result<HttpResponse, TransportError>) {
+        if let Some(key) = self.key.take() {
+            for waiter in self.in_flight.take(&key) {
+                let _ = waiter.send(result.clone());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.in_flight.take(&key);
+        }
+    }
+}
+
+// The fingerprint leaves out `apiKey`, but callers with different keys may get
+// different answers, e.g. an invalid key error, so a hash of the key goes in as well
+#[cfg(feature = "async")]
+fn coalescing_key(config: &RequestConfig) -> String {
+    let mut hasher = DefaultHasher::new();
+    config
+        .params
+        .iter()
+        .find(|(key, _)| key == "apiKey")
+        .map(|(_, value)| value)
+        .hash(&mut hasher);
+    format!("{:016x} {}", hasher.finish(), config.fingerprint())
+}
+
+/// Executes `RequestConfig`s over an `AsyncTransport`.
+#[cfg(feature = "async")]
+#[derive(Debug, Default)]
+pub struct AsyncClient<T> {
+    transport: T,
+    config: ClientConfig,
+    limiter: Option<RateLimiter>,
+    retry: Option<RetryPolicy>,
+    cache: Option<ResponseCache>,
+    in_flight: Option<InFlight>,
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncTransport> AsyncClient<T> {
+    #[must_use]
+    pub fn new(transport: T) -> Self {
+        AsyncClient {
+            transport,
+            config: ClientConfig::default(),
+            limiter: None,
+            retry: None,
+            cache: None,
+            in_flight: None,
+        }
+    }
+
+    // The transport keeps the timeouts it was built with, see `from_config`
+    #[must_use]
+    pub fn with_config(mut self, config: ClientConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    // Paces every request; pass a clone to share the limit with other clients
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    // Re-sends GET requests that fail for a transient reason
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    // Serves repeated GET requests from `cache` while their route TTL lasts
+    #[must_use]
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    // Identical GET requests issued with the same key while one is in flight share
+    // its response
+    #[must_use]
+    pub fn with_coalescing(mut self) -> Self {
+        self.in_flight = Some(InFlight::default());
+        self
+    }
+
+    #[must_use]
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    #[must_use]
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    async fn send_once(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError> {
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire_async().await;
+        }
+        self.transport.send(config).await
+    }
+
+    async fn send(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError> {
+        let in_flight = match &self.in_flight {
+            Some(in_flight) if config.method.is_idempotent() => in_flight,
+            _ => return self.send_once(config).await,
+        };
+        let key = coalescing_key(config);
+        if let Some(receiver) = in_flight.join(&key) {
+            // A cancelled leader leaves the waiter to send on its own
+            return match receiver.await {
+                Ok(result) => result,
+                Err(_) => self.send_once(config).await,
+            };
+        }
+        let leader = Leader {
+            in_flight,
+            key: Some(key),
+        };
+        let result = self.send_once(config).await;
+        leader.finish(&result);
+        result
+    }
+
+    /// # Errors
+    /// Fails when the transport fails or the body does not deserialize into `R`.
+    pub async fn execute<R: DeserializeOwned>(
+        &self,
+        config: &RequestConfig,
+    ) -> Result<R, EthplorerError> {
+        self.execute_parsed(config).await.map(Parsed::into_inner)
+    }
+
+    /// Like `execute`, keeping the warnings of a lenient parse.
+    ///
+    /// # Errors
+    /// Fails when the transport fails or the body does not deserialize into `R`.
+    pub async fn execute_parsed<R: DeserializeOwned>(
+        &self,
+        config: &RequestConfig,
+    ) -> Result<Parsed<R>, EthplorerError> {
+        let violations = check_limits(&self.config, config)?;
+        if let Some(value) = from_cache::<R>(self.cache.as_ref(), config, &self.config) {
+            return Ok(value.with_limit_violations(violations));
+        }
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let (result, hint) = match self.send(config).await {
+                Ok(response) => (
+                    receive::<R>(self.cache.as_ref(), config, &response, &self.config),
+                    retry_after(&response),
+                ),
+                Err(err) => (Err(err.into()), None),
+            };
+            let err = match result {
+                Ok(value) => return Ok(value.with_limit_violations(violations)),
+                Err(err) => err,
+            };
+            let delay = match &self.retry {
+                Some(retry) if retry.should_retry(config.method.is_idempotent(), attempt, &err) => {
+                    retry.delay(attempt, hint)
+                }
+                _ => return Err(with_attempts(err, attempt)),
+            };
+            let Some(delay) = delay else {
+                return Err(gave_up(err, attempt, hint));
+            };
+            futures_timer::Delay::new(delay).await;
+        }
+    }
+
+    /// # Errors
+    /// Fails when the transport fails or the body does not deserialize into the
+    /// endpoint's response type.
+    pub async fn call<E: Endpoint>(&self, endpoint: E) -> Result<E::Response, EthplorerError> {
+        self.execute(&endpoint.to_config(&self.config)).await
+    }
+
+    /// # Errors
+    /// Same as `call`.
+    pub async fn call_parsed<E: Endpoint>(
+        &self,
+        endpoint: E,
+    ) -> Result<Parsed<E::Response>, EthplorerError> {
+        self.execute_parsed(&endpoint.to_config(&self.config)).await
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncClient<ReqwestTransport> {
+    /// Builds the transport from `config` as well, so its timeouts take effect.
+    ///
+    /// # Errors
+    /// Fails when `reqwest` cannot initialise its TLS backend.
+    pub fn from_config(config: ClientConfig) -> Result<Self, TransportError> {
+        let transport = ReqwestTransport::from_config(&config)?;
+        Ok(AsyncClient::new(transport).with_config(config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::MemoryCache;
+    use crate::error::ApiErrorCode;
+    use crate::rate_limit::{Plan, Quota};
+    use crate::strictness::{ParseWarning, Strictness};
+    use crate::transport::MemoryTransport;
+    use crate::types::Method;
+    use crate::types::Timestamp;
+    use crate::{
+        get_last_block_config, get_top_token_holders_config, GetTxInfo, LastBlock, TopTokenHolders,
+    };
+    use std::time::Duration;
+
+    fn transport() -> MemoryTransport {
+        MemoryTransport::new().with_response(
+            "https://api.ethplorer.io/getLastBlock",
+            HttpResponse::new(200, r#"{"lastBlock":13456789}"#),
+        )
+    }
+
+    #[test]
+    fn client_execute_works() {
+        let client = Client::new(transport());
+        let block: LastBlock = client
+            .execute(&get_last_block_config(&ClientConfig::default()))
+            .unwrap();
+        assert_eq!(block.last_block, 13_456_789);
+    }
+
+    #[test]
+    fn client_execute_reports_bad_body() {
+        let client =
+            Client::new(MemoryTransport::new().with_fallback(HttpResponse::new(200, "not json")));
+        let result = client.execute::<LastBlock>(&get_last_block_config(&ClientConfig::default()));
+        match result {
+            Err(EthplorerError::Deserialize { body, .. }) => assert_eq!(body, "not json"),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn client_execute_reports_api_error() {
+        let client = Client::new(MemoryTransport::new().with_fallback(HttpResponse::new(
+            400,
+            r#"{"error":{"code":104,"message":"Invalid address format"}}"#,
+        )));
+        let result = client.execute::<LastBlock>(&get_last_block_config(&ClientConfig::default()));
+        match result {
+            Err(EthplorerError::Api(err)) => {
+                assert_eq!(err.code, ApiErrorCode::InvalidAddress);
+                assert_eq!(err.message, "Invalid address format");
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn client_execute_reports_status() {
+        let client = Client::new(
+            MemoryTransport::new().with_fallback(HttpResponse::new(502, "Bad Gateway")),
+        );
+        let result = client.execute::<LastBlock>(&get_last_block_config(&ClientConfig::default()));
+        match result {
+            Err(EthplorerError::Status { status, body }) => {
+                assert_eq!(status, 502);
+                assert_eq!(body, "Bad Gateway");
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn client_rate_limiter_is_shared() {
+        let limiter = RateLimiter::new(&Plan::Custom(vec![Quota::per_hour(2)]));
+        let first = Client::new(transport()).with_rate_limiter(limiter.clone());
+        let second = Client::new(transport()).with_rate_limiter(limiter.clone());
+        let config = get_last_block_config(&ClientConfig::default());
+        first.execute::<LastBlock>(&config).unwrap();
+        second.execute::<LastBlock>(&config).unwrap();
+        assert!(limiter.try_acquire().is_err());
+    }
+
+    #[test]
+    fn client_rejects_params_past_plan_limits() {
+        let client = Client::new(
+            MemoryTransport::new().with_fallback(HttpResponse::new(200, r#"{"holders":[]}"#)),
+        );
+        let address = "0xdac17f958d2ee523a2206206994597c13d831ec7"
+            .parse()
+            .unwrap();
+        let client = client.with_config(ClientConfig::new().with_limit_policy(LimitPolicy::Reject));
+        let request = get_top_token_holders_config(client.config(), &address, 500);
+        match client.execute::<TopTokenHolders>(&request) {
+            Err(EthplorerError::InvalidParams(violations)) => assert_eq!(violations[0].max, 100),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(client.transport().requests().is_empty());
+
+        let client = client.with_config(ClientConfig::new().with_limit_policy(LimitPolicy::Warn));
+        let parsed = client.execute_parsed::<TopTokenHolders>(&request).unwrap();
+        assert_eq!(parsed.limit_violations[0].value, 500);
+        assert!(!parsed.is_clean());
+
+        // Clamping stays the default, so the builders never exceed the plan
+        let client = client.with_config(ClientConfig::new());
+        let request = get_top_token_holders_config(client.config(), &address, 500);
+        assert_eq!(request.params[1].1, "100");
+        assert!(client
+            .execute_parsed::<TopTokenHolders>(&request)
+            .unwrap()
+            .is_clean());
+    }
+
+    #[test]
+    fn client_serves_cached_responses() {
+        let cache = ResponseCache::new(MemoryCache::new(10));
+        let client = Client::new(transport()).with_cache(cache.clone());
+        let config = get_last_block_config(&ClientConfig::new().with_api_key("a"));
+        let other_key = get_last_block_config(&ClientConfig::new().with_api_key("b"));
+        client.execute::<LastBlock>(&config).unwrap();
+        let block: LastBlock = client.execute(&other_key).unwrap();
+        assert_eq!(block.last_block, 13_456_789);
+        assert_eq!(client.transport().requests().len(), 1);
+
+        cache.invalidate(&config);
+        client.execute::<LastBlock>(&config).unwrap();
+        assert_eq!(client.transport().requests().len(), 2);
+    }
+
+    #[test]
+    fn client_strictness_decides_on_bad_dates() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        let body = format!(
+            r#"{{"hash":"{hash}","timestamp":"yesterday","from":"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"}}"#
+        );
+        let transport =
+            || MemoryTransport::new().with_fallback(HttpResponse::new(200, body.clone()));
+        let endpoint = || GetTxInfo {
+            hash: hash.parse().unwrap(),
+        };
+
+        let parsed = Client::new(transport()).call_parsed(endpoint()).unwrap();
+        assert_eq!(parsed.timestamp, Timestamp::default());
+        assert_eq!(
+            parsed.warnings,
+            vec![ParseWarning::new("yesterday", "unrecognised date")]
+        );
+
+        let strict = Client::new(transport())
+            .with_config(ClientConfig::new().with_strictness(Strictness::Strict));
+        match strict.call(endpoint()) {
+            Err(EthplorerError::Deserialize { source, .. }) => {
+                assert!(source
+                    .to_string()
+                    .contains(r#"unrecognised date "yesterday""#));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn client_keeps_unknown_fields_on_request() {
+        let transport = || {
+            MemoryTransport::new().with_fallback(HttpResponse::new(
+                200,
+                r#"{"lastBlock":13456789,"lastBlockTime":1700000000}"#,
+            ))
+        };
+        let request = get_last_block_config(&ClientConfig::default());
+
+        let block: LastBlock = Client::new(transport()).execute(&request).unwrap();
+        assert!(block.unknown.is_empty());
+
+        let client =
+            Client::new(transport()).with_config(ClientConfig::new().with_unknown_fields(true));
+        let block: LastBlock = client.execute(&request).unwrap();
+        assert_eq!(block.last_block, 13_456_789);
+        assert_eq!(
+            block.unknown.get("lastBlockTime"),
+            Some(&serde_json::json!(1_700_000_000))
+        );
+    }
+
+    #[test]
+    fn client_retries_transient_errors() {
+        let url = "https://api.ethplorer.io/getLastBlock";
+        let transport = MemoryTransport::new()
+            .with_response(
+                url,
+                HttpResponse::new(503, "").with_header("Retry-After", "0"),
+            )
+            .with_response(
+                url,
+                HttpResponse::new(400, r#"{"error":{"code":3,"message":"Limit exceeded"}}"#),
+            )
+            .with_response(url, HttpResponse::new(200, r#"{"lastBlock":1}"#));
+        let retry = RetryPolicy::new()
+            .with_max_attempts(3)
+            .with_base_delay(Duration::from_millis(1));
+        let client = Client::new(transport).with_retry_policy(retry.clone());
+        let config = get_last_block_config(&ClientConfig::default());
+        let block: LastBlock = client.execute(&config).unwrap();
+        assert_eq!(block.last_block, 1);
+        assert_eq!(client.transport().requests().len(), 3);
+
+        let client = Client::new(MemoryTransport::new().with_fallback(HttpResponse::new(502, "")))
+            .with_retry_policy(retry.clone());
+        match client.execute::<LastBlock>(&config) {
+            Err(EthplorerError::Retried {
+                attempts,
+                last,
+                retry_after: None,
+            }) => {
+                assert_eq!(attempts, 3);
+                assert!(matches!(*last, EthplorerError::Status { status: 502, .. }));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        // A `Retry-After` past `max_delay` gives up rather than retrying early
+        let client = Client::new(
+            MemoryTransport::new()
+                .with_fallback(HttpResponse::new(429, "").with_header("Retry-After", "3600")),
+        )
+        .with_retry_policy(retry.clone());
+        match client.execute::<LastBlock>(&config) {
+            Err(EthplorerError::Retried {
+                attempts,
+                retry_after,
+                ..
+            }) => {
+                assert_eq!(attempts, 1);
+                assert_eq!(retry_after, Some(Duration::from_secs(3600)));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(client.transport().requests().len(), 1);
+
+        let post = RequestConfig {
+            method: Method::Post,
+            ..config
+        };
+        let client = Client::new(MemoryTransport::new().with_fallback(HttpResponse::new(502, "")))
+            .with_retry_policy(retry);
+        let result = client.execute::<LastBlock>(&post);
+        assert!(matches!(result, Err(EthplorerError::Status { .. })));
+        assert_eq!(client.transport().requests().len(), 1);
+    }
+
+    #[cfg(feature = "async")]
+    #[derive(Debug, Default)]
+    struct SlowTransport(MemoryTransport);
+
+    #[cfg(feature = "async")]
+    #[async_trait::async_trait]
+    impl AsyncTransport for SlowTransport {
+        async fn send(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError> {
+            futures_timer::Delay::new(Duration::from_millis(20)).await;
+            Transport::send(&self.0, config)
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_client_coalesces_identical_requests() {
+        let client = AsyncClient::new(SlowTransport(transport())).with_coalescing();
+        let first = get_last_block_config(&ClientConfig::new().with_api_key("a"));
+        let second = get_last_block_config(&ClientConfig::new().with_api_key("a"));
+        let (first, second) = futures::executor::block_on(async {
+            futures::join!(
+                client.execute::<LastBlock>(&first),
+                client.execute::<LastBlock>(&second)
+            )
+        });
+        assert_eq!(first.unwrap().last_block, 13_456_789);
+        assert_eq!(second.unwrap().last_block, 13_456_789);
+        assert_eq!(client.transport().0.requests().len(), 1);
+
+        // Another key may be refused where the first wasn't, so it goes out on its own
+        let client = AsyncClient::new(SlowTransport(transport())).with_coalescing();
+        let first = get_last_block_config(&ClientConfig::new().with_api_key("a"));
+        let second = get_last_block_config(&ClientConfig::new().with_api_key("b"));
+        let (first, second) = futures::executor::block_on(async {
+            futures::join!(
+                client.execute::<LastBlock>(&first),
+                client.execute::<LastBlock>(&second)
+            )
+        });
+        assert!(first.is_ok() && second.is_ok());
+        assert_eq!(client.transport().0.requests().len(), 2);
+
+        let client = AsyncClient::new(SlowTransport::default()).with_coalescing();
+        let config = get_last_block_config(&ClientConfig::default());
+        let results = futures::executor::block_on(async {
+            futures::join!(
+                client.execute::<LastBlock>(&config),
+                client.execute::<LastBlock>(&config),
+                client.execute::<LastBlock>(&config)
+            )
+        });
+        for result in &[results.0, results.1, results.2] {
+            assert!(matches!(result, Err(EthplorerError::Transport(_))));
+        }
+        assert_eq!(client.transport().0.requests().len(), 1);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_client_execute_works() {
+        let client = AsyncClient::new(transport());
+        let block: LastBlock = futures::executor::block_on(
+            client.execute(&get_last_block_config(&ClientConfig::default())),
+        )
+        .unwrap();
+        assert_eq!(block.last_block, 13_456_789);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn client_from_config_applies_timeout() {
+        // Accepts connections but never answers them
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = ClientConfig::new()
+            .with_base_url(&format!("http://{}", listener.local_addr().unwrap()))
+            .with_timeout(Duration::from_millis(200));
+        let client = Client::from_config(config.clone());
+        assert_eq!(client.config(), &config);
+
+        let started = std::time::Instant::now();
+        let result: Result<LastBlock, _> = client.execute(&get_last_block_config(&config));
+        assert!(matches!(result, Err(EthplorerError::Transport(_))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_client_from_config_keeps_config() {
+        let config = ClientConfig::new()
+            .with_timeout(Duration::from_secs(3))
+            .with_connect_timeout(Duration::from_secs(1));
+        let client = AsyncClient::from_config(config.clone()).unwrap();
+        assert_eq!(client.config(), &config);
+    }
+}
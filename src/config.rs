@@ -0,0 +1,286 @@
+use crate::api_key_param;
+use crate::consts::{MONITOR_NETWORK, NETWORK, POOL_ROUTES};
+use crate::rate_limit::Plan;
+use crate::strictness::Strictness;
+use crate::types::RequestConfig;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+// What happens to a `limit` or `period` param beyond the plan's maximum
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LimitPolicy {
+    // The client refuses to send the request
+    Reject,
+    // The request is sent as is; the violations come back on `Parsed::limit_violations`
+    Warn,
+    // Builders quietly lower the value to the maximum, as they always have
+    #[default]
+    Clamp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitViolation {
+    pub param: String,
+    pub value: u64,
+    pub max: u64,
+}
+
+impl Display for LimitViolation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} exceeds the plan maximum of {}",
+            self.param, self.value, self.max
+        )
+    }
+}
+
+/// Client-level settings shared by every request built through the `get_*_config` functions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientConfig {
+    pub base_url: String,
+    // Host of the bulk API monitor (pool) routes
+    pub monitor_base_url: String,
+    pub api_key: String,
+    // Both timeouts are applied when a transport is built from this config, by its
+    // `from_config` or the client's; swapping the config of an existing client
+    // leaves its transport's timeouts as they were
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    pub route_overrides: HashMap<String, String>,
+    // Falls back to the plan implied by `api_key`
+    pub plan: Option<Plan>,
+    pub limit_policy: LimitPolicy,
+    // How responses treat values that only parse by guessing, e.g. malformed dates
+    pub strictness: Strictness,
+    // Fills each response struct's `unknown` field with the keys it doesn't know
+    pub unknown_fields: bool,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            base_url: NETWORK.to_string(),
+            monitor_base_url: MONITOR_NETWORK.to_string(),
+            api_key: String::new(),
+            timeout: None,
+            connect_timeout: None,
+            route_overrides: HashMap::new(),
+            plan: None,
+            limit_policy: LimitPolicy::default(),
+            strictness: Strictness::default(),
+            unknown_fields: false,
+        }
+    }
+}
+
+impl ClientConfig {
+    #[must_use]
+    pub fn new() -> Self {
+        ClientConfig::default()
+    }
+
+    #[must_use]
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.api_key = api_key.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn with_monitor_base_url(mut self, base_url: &str) -> Self {
+        self.monitor_base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    #[must_use]
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    // Sends a single route to a different host, e.g. a local mock or caching proxy
+    #[must_use]
+    pub fn with_route_base_url(mut self, route: &str, base_url: &str) -> Self {
+        self.route_overrides.insert(
+            route.to_string(),
+            base_url.trim_end_matches('/').to_string(),
+        );
+        self
+    }
+
+    #[must_use]
+    pub fn with_plan(mut self, plan: Plan) -> Self {
+        self.plan = Some(plan);
+        self
+    }
+
+    #[must_use]
+    pub fn with_limit_policy(mut self, limit_policy: LimitPolicy) -> Self {
+        self.limit_policy = limit_policy;
+        self
+    }
+
+    #[must_use]
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    #[must_use]
+    pub fn with_unknown_fields(mut self, unknown_fields: bool) -> Self {
+        self.unknown_fields = unknown_fields;
+        self
+    }
+
+    #[must_use]
+    pub fn plan(&self) -> Plan {
+        self.plan
+            .clone()
+            .unwrap_or_else(|| Plan::for_api_key(&self.api_key))
+    }
+
+    // Lowers `limit` to the plan maximum under `LimitPolicy::Clamp` only
+    #[must_use]
+    pub fn apply_limit(&self, limit: u64) -> u64 {
+        match self.limit_policy {
+            LimitPolicy::Clamp => limit.min(self.plan().max_limit()),
+            _ => limit,
+        }
+    }
+
+    // Lowers `period` to the plan maximum under `LimitPolicy::Clamp` only
+    #[must_use]
+    pub fn apply_period(&self, period: u64) -> u64 {
+        match self.limit_policy {
+            LimitPolicy::Clamp => period.min(self.plan().max_period()),
+            _ => period,
+        }
+    }
+
+    // Params of `request` that go past the plan's limits; pool routes count their
+    // `period` in seconds and are not bound by the plan
+    #[must_use]
+    pub fn check_limits(&self, request: &RequestConfig) -> Vec<LimitViolation> {
+        let is_pool = request
+            .routes
+            .first()
+            .is_some_and(|route| POOL_ROUTES.contains(&route.as_str()));
+        if is_pool {
+            return vec![];
+        }
+        let plan = self.plan();
+        request
+            .params
+            .iter()
+            .filter_map(|(name, value)| {
+                let max = match name.as_str() {
+                    "limit" => plan.max_limit(),
+                    "period" => plan.max_period(),
+                    _ => return None,
+                };
+                let value = value.parse::<u64>().ok()?;
+                Some(LimitViolation {
+                    param: name.clone(),
+                    value,
+                    max,
+                })
+                .filter(|violation| violation.value > violation.max)
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn network_for(&self, route: &str) -> &str {
+        self.route_overrides
+            .get(route)
+            .map_or(self.base_url.as_str(), String::as_str)
+    }
+
+    // Same as `network_for` for the pool routes, which default to the monitor host
+    #[must_use]
+    pub fn monitor_network_for(&self, route: &str) -> &str {
+        self.route_overrides
+            .get(route)
+            .map_or(self.monitor_base_url.as_str(), String::as_str)
+    }
+
+    #[must_use]
+    pub fn api_key_param(&self) -> (String, String) {
+        api_key_param(&self.api_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::{CREATE_POOL_ROUTE, GET_TOKEN_INFO_ROUTE, GET_TOP_ROUTE};
+    use crate::get_top_token_holders_config;
+
+    #[test]
+    fn client_config_network_for_works() {
+        let config = ClientConfig::new()
+            .with_base_url("http://localhost:8080/")
+            .with_route_base_url(GET_TOP_ROUTE, "http://proxy");
+        assert_eq!(
+            config.network_for(GET_TOKEN_INFO_ROUTE),
+            "http://localhost:8080"
+        );
+        assert_eq!(config.network_for(GET_TOP_ROUTE), "http://proxy");
+        assert_eq!(ClientConfig::default().network_for(GET_TOP_ROUTE), NETWORK);
+        assert_eq!(
+            config.monitor_network_for(CREATE_POOL_ROUTE),
+            MONITOR_NETWORK
+        );
+        assert_eq!(
+            config
+                .with_monitor_base_url("http://monitor/")
+                .monitor_network_for(CREATE_POOL_ROUTE),
+            "http://monitor"
+        );
+    }
+
+    #[test]
+    fn client_config_limits_work() {
+        let address = "0xdac17f958d2ee523a2206206994597c13d831ec7"
+            .parse()
+            .unwrap();
+        assert_eq!(ClientConfig::new().limit_policy, LimitPolicy::Clamp);
+        let config = ClientConfig::new().with_limit_policy(LimitPolicy::Warn);
+        let request = get_top_token_holders_config(&config, &address, 5000);
+        assert_eq!(request.params[1], ("limit".to_string(), "5000".to_string()));
+        assert_eq!(
+            config.check_limits(&request),
+            vec![LimitViolation {
+                param: "limit".to_string(),
+                value: 5000,
+                max: 100,
+            }]
+        );
+        assert_eq!(
+            config.check_limits(&request)[0].to_string(),
+            "limit 5000 exceeds the plan maximum of 100"
+        );
+
+        let config = ClientConfig::new()
+            .with_api_key("key")
+            .with_limit_policy(LimitPolicy::Clamp);
+        let request = get_top_token_holders_config(&config, &address, 5000);
+        assert_eq!(request.params[1], ("limit".to_string(), "1000".to_string()));
+        assert!(config.check_limits(&request).is_empty());
+        assert_eq!(config.with_plan(Plan::Freekey).apply_period(365), 30);
+    }
+}
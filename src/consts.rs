@@ -1,7 +1,16 @@
+pub const ADD_POOL_ADDRESSES_ROUTE: &str = "addPoolAddresses";
+pub const CLEAR_POOL_ADDRESSES_ROUTE: &str = "clearPoolAddresses";
+pub const CREATE_POOL_ROUTE: &str = "createPool";
+pub const DELETE_POOL_ADDRESSES_ROUTE: &str = "deletePoolAddresses";
+pub const DELETE_POOL_ROUTE: &str = "deletePool";
 pub const GET_ADDRESS_HISTORY: &str = "getAddressHistory";
 pub const GET_ADDRESS_INFO_ROUTE: &str = "getAddressInfo";
 pub const GET_ADDRESS_TRANSACTIONS_ROUTE: &str = "getAddressTransactions";
 pub const GET_LAST_BLOCK_ROUTE: &str = "getLastBlock";
+pub const GET_POOL_ADDRESSES_ROUTE: &str = "getPoolAddresses";
+pub const GET_POOL_LAST_OPERATIONS_ROUTE: &str = "getPoolLastOperations";
+pub const GET_POOL_LAST_TRANSACTIONS_ROUTE: &str = "getPoolLastTransactions";
+pub const GET_POOL_UPDATES_ROUTE: &str = "getPoolUpdates";
 pub const GET_TOKENS_NEW_ROUTE: &str = "getTokensNew";
 pub const GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE: &str = "getTokenHistoryGrouped";
 pub const GET_TOKEN_HISTORY_ROUTE: &str = "getTokenHistory";
@@ -10,4 +19,17 @@ pub const GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE: &str = "getTokenPriceHistoryGro
 pub const GET_TOP_ROUTE: &str = "getTop";
 pub const GET_TOP_TOKENS_ROUTE: &str = "getTopTokens";
 pub const GET_TOP_TOKEN_HOLDERS_ROUTE: &str = "getTopTokenHolders";
+pub const GET_TX_INFO_ROUTE: &str = "getTxInfo";
 pub const NETWORK: &str = "https://api.ethplorer.io";
+pub const MONITOR_NETWORK: &str = "https://api-mon.ethplorer.io";
+pub const POOL_ROUTES: [&str; 9] = [
+    ADD_POOL_ADDRESSES_ROUTE,
+    CLEAR_POOL_ADDRESSES_ROUTE,
+    CREATE_POOL_ROUTE,
+    DELETE_POOL_ADDRESSES_ROUTE,
+    DELETE_POOL_ROUTE,
+    GET_POOL_ADDRESSES_ROUTE,
+    GET_POOL_LAST_OPERATIONS_ROUTE,
+    GET_POOL_LAST_TRANSACTIONS_ROUTE,
+    GET_POOL_UPDATES_ROUTE,
+];
@@ -0,0 +1,589 @@
+use serde::de::{
+    DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::{de, Deserialize, Deserializer};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+
+// Name a struct gives its `UnknownFields` to have the tracker fill it in
+const UNKNOWN_KEY: &str = "$unknown";
+
+/// Keys a response carried that its type doesn't know about, by name. Only filled
+/// in when parsing with `ClientConfig::with_unknown_fields` or `from_slice_keeping_unknown`.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct UnknownFields(BTreeMap<String, Value>);
+
+impl UnknownFields {
+    #[must_use]
+    pub fn into_inner(self) -> BTreeMap<String, Value> {
+        self.0
+    }
+}
+
+impl Deref for UnknownFields {
+    type Target = BTreeMap<String, Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// How one response type's fields compared to what came over the wire.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeDrift {
+    // Objects parsed as this type
+    pub seen: u64,
+    // Keys the type doesn't know, with how many objects carried them
+    pub new_fields: BTreeMap<String, u64>,
+    // Known fields left to their default, with how many objects lacked them
+    pub missing_fields: BTreeMap<String, u64>,
+}
+
+impl TypeDrift {
+    #[must_use]
+    pub fn has_drift(&self) -> bool {
+        !self.new_fields.is_empty() || !self.missing_fields.is_empty()
+    }
+
+    fn merge(&mut self, other: TypeDrift) {
+        self.seen += other.seen;
+        for (field, count) in other.new_fields {
+            *self.new_fields.entry(field).or_default() += count;
+        }
+        for (field, count) in other.missing_fields {
+            *self.missing_fields.entry(field).or_default() += count;
+        }
+    }
+}
+
+/// Fields that appeared or went missing across a set of responses, per response
+/// type. Structs behind a custom deserializer, such as the price inside `Pricing`,
+/// are tracked as long as it parses them with `tracked_from_value`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriftReport {
+    pub responses: u64,
+    pub types: BTreeMap<String, TypeDrift>,
+}
+
+impl DriftReport {
+    #[must_use]
+    pub fn new() -> Self {
+        DriftReport::default()
+    }
+
+    /// Parses a recorded body as `T` and adds its fields to the report.
+    ///
+    /// # Errors
+    /// Fails when the body is not a valid `T`; nothing is recorded then.
+    pub fn record<T: DeserializeOwned>(&mut self, body: &[u8]) -> Result<T, serde_json::Error> {
+        let (value, types) = parse_tracked(body, false)?;
+        self.responses += 1;
+        for (name, drift) in types {
+            self.types.entry(name).or_default().merge(drift);
+        }
+        Ok(value)
+    }
+
+    #[must_use]
+    pub fn get(&self, type_name: &str) -> Option<&TypeDrift> {
+        self.types.get(type_name)
+    }
+
+    #[must_use]
+    pub fn has_drift(&self) -> bool {
+        self.types.values().any(TypeDrift::has_drift)
+    }
+}
+
+// One line per drifting type, e.g. `TokenInfo (3 seen): new website (2); missing owner (3)`
+impl Display for DriftReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let counted = |fields: &BTreeMap<String, u64>| {
+            fields
+                .iter()
+                .map(|(field, count)| format!("{field} ({count})"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        for (name, drift) in self.types.iter().filter(|(_, drift)| drift.has_drift()) {
+            write!(f, "{} ({} seen):", name, drift.seen)?;
+            if !drift.new_fields.is_empty() {
+                write!(f, " new {};", counted(&drift.new_fields))?;
+            }
+            if !drift.missing_fields.is_empty() {
+                write!(f, " missing {};", counted(&drift.missing_fields))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses `body` as `T`, keeping unknown keys in each struct's `unknown` field.
+///
+/// # Errors
+/// Fails when the body is not a valid `T`.
+pub fn from_slice_keeping_unknown<T: DeserializeOwned>(
+    body: &[u8],
+) -> Result<T, serde_json::Error> {
+    parse_tracked(body, true).map(|(value, _)| value)
+}
+
+#[derive(Default)]
+struct Recorder {
+    keep_unknown: bool,
+    types: BTreeMap<String, TypeDrift>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+// Restores the enclosing recorder even if the parse panics
+struct Scope {
+    previous: Option<Recorder>,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        RECORDER.with(|recorder| recorder.replace(previous));
+    }
+}
+
+fn parse_tracked<T: DeserializeOwned>(
+    body: &[u8],
+    keep_unknown: bool,
+) -> Result<(T, BTreeMap<String, TypeDrift>), serde_json::Error> {
+    let recorder = Recorder {
+        keep_unknown,
+        types: BTreeMap::new(),
+    };
+    let scope = Scope {
+        previous: RECORDER.with(|current| current.replace(Some(recorder))),
+    };
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    let value = T::deserialize(Tracked {
+        inner: &mut deserializer,
+    })
+    .and_then(|value| deserializer.end().map(|()| value));
+    let types = RECORDER
+        .with(|current| current.borrow_mut().take())
+        .map(|recorder| recorder.types)
+        .unwrap_or_default();
+    drop(scope);
+    Ok((value?, types))
+}
+
+// For deserializers that buffer a value before picking a type for it, which would
+// otherwise hide that type from the report. Outside of a tracked parse this is a
+// plain `T::deserialize`.
+pub(crate) fn tracked_from_value<T: DeserializeOwned>(
+    value: &Value,
+) -> Result<T, serde_json::Error> {
+    if RECORDER.with(|current| current.borrow().is_some()) {
+        T::deserialize(Tracked { inner: value })
+    } else {
+        T::deserialize(value)
+    }
+}
+
+fn keeps_unknown() -> bool {
+    RECORDER.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(|recorder| recorder.keep_unknown)
+    })
+}
+
+// Wraps a deserializer so every struct below it reports the keys it saw
+struct Tracked<D> {
+    inner: D,
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+            let visitor = TrackedVisitor::new(visitor, None);
+            self.inner.$method(visitor)
+        }
+    )*};
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Tracked<D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32
+        deserialize_u64 deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char
+        deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let visitor = TrackedVisitor::new(visitor, None);
+        self.inner.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let visitor = TrackedVisitor::new(visitor, None);
+        self.inner.deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let visitor = TrackedVisitor::new(visitor, None);
+        self.inner.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let visitor = TrackedVisitor::new(visitor, None);
+        self.inner.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let visitor = TrackedVisitor::new(visitor, Some(Shape { name, fields }));
+        self.inner.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let visitor = TrackedVisitor::new(visitor, None);
+        self.inner.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+// The struct a map is being read into
+#[derive(Clone, Copy)]
+struct Shape {
+    name: &'static str,
+    fields: &'static [&'static str],
+}
+
+impl Shape {
+    fn knows(&self, key: &str) -> bool {
+        self.fields.contains(&key)
+    }
+}
+
+struct TrackedVisitor<V> {
+    inner: V,
+    shape: Option<Shape>,
+}
+
+impl<V> TrackedVisitor<V> {
+    fn new(inner: V, shape: Option<Shape>) -> Self {
+        TrackedVisitor { inner, shape }
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident: $ty:ty)*) => {$(
+        fn $method<E: de::Error>(self, v: $ty) -> Result<V::Value, E> {
+            self.inner.$method(v)
+        }
+    )*};
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for TrackedVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(fmtr)
+    }
+
+    forward_visit! {
+        visit_bool: bool visit_i8: i8 visit_i16: i16 visit_i32: i32 visit_i64: i64
+        visit_i128: i128 visit_u8: u8 visit_u16: u16 visit_u32: u32 visit_u64: u64
+        visit_u128: u128 visit_f32: f32 visit_f64: f64 visit_char: char visit_str: &str
+        visit_borrowed_str: &'de str visit_string: String visit_bytes: &[u8]
+        visit_borrowed_bytes: &'de [u8] visit_byte_buf: Vec<u8>
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<V::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        self.inner.visit_some(Tracked {
+            inner: deserializer,
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        self.inner.visit_newtype_struct(Tracked {
+            inner: deserializer,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        self.inner.visit_seq(TrackedSeq { inner: seq })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        let mut map = TrackedMap {
+            inner: map,
+            shape: self.shape,
+            present: vec![],
+            unknown: BTreeMap::new(),
+            pending: Pending::Known,
+        };
+        let value = self.inner.visit_map(&mut map)?;
+        map.finish();
+        Ok(value)
+    }
+
+    // Enum payloads aren't tracked; no response type carries a struct inside one
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        self.inner.visit_enum(data)
+    }
+}
+
+struct TrackedSeed<S> {
+    inner: S,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for TrackedSeed<S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        self.inner.deserialize(Tracked {
+            inner: deserializer,
+        })
+    }
+}
+
+struct TrackedSeq<A> {
+    inner: A,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for TrackedSeq<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, A::Error> {
+        self.inner.next_element_seed(TrackedSeed { inner: seed })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+// What the value after the last key handed out belongs to
+enum Pending {
+    Known,
+    Unknown(String),
+    SideMap,
+}
+
+struct TrackedMap<A> {
+    inner: A,
+    shape: Option<Shape>,
+    present: Vec<&'static str>,
+    unknown: BTreeMap<String, Value>,
+    pending: Pending,
+}
+
+impl<A> TrackedMap<A> {
+    // Once the struct has its value, adds what the map held to the report
+    fn finish(self) {
+        let Some(shape) = self.shape else {
+            return;
+        };
+        RECORDER.with(|current| {
+            if let Some(recorder) = current.borrow_mut().as_mut() {
+                recorder
+                    .types
+                    .entry(shape.name.to_string())
+                    .or_default()
+                    .merge(self.drift(shape));
+            }
+        });
+    }
+
+    fn drift(&self, shape: Shape) -> TypeDrift {
+        let mut drift = TypeDrift {
+            seen: 1,
+            ..TypeDrift::default()
+        };
+        for field in shape.fields {
+            if *field != UNKNOWN_KEY && !self.present.contains(field) {
+                drift.missing_fields.insert((*field).to_string(), 1);
+            }
+        }
+        for key in self.unknown.keys() {
+            drift.new_fields.insert(key.clone(), 1);
+        }
+        drift
+    }
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for TrackedMap<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        let Some(shape) = self.shape else {
+            return self.inner.next_key_seed(seed);
+        };
+        if let Pending::SideMap = self.pending {
+            return Ok(None);
+        }
+        if let Some(key) = self.inner.next_key::<String>()? {
+            self.pending = match shape.fields.iter().find(|field| **field == key) {
+                Some(field) => {
+                    self.present.push(*field);
+                    Pending::Known
+                }
+                None => Pending::Unknown(key.clone()),
+            };
+            return seed.deserialize(key.into_deserializer()).map(Some);
+        }
+        // The struct's side map goes last, once every real key has been read
+        if keeps_unknown() && shape.knows(UNKNOWN_KEY) && !self.unknown.is_empty() {
+            self.pending = Pending::SideMap;
+            return seed.deserialize(UNKNOWN_KEY.into_deserializer()).map(Some);
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, A::Error> {
+        match &self.pending {
+            Pending::Known => self.inner.next_value_seed(TrackedSeed { inner: seed }),
+            Pending::Unknown(key) => {
+                let value: Value = self.inner.next_value()?;
+                self.unknown.insert(key.clone(), value.clone());
+                seed.deserialize(value).map_err(de::Error::custom)
+            }
+            Pending::SideMap => {
+                let fields = self.unknown.clone().into_iter().collect();
+                seed.deserialize(Value::Object(fields))
+                    .map_err(de::Error::custom)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Debug, Default)]
+    struct Inner {
+        name: String,
+        #[serde(default)]
+        owner: String,
+        #[serde(rename = "$unknown", default)]
+        unknown: UnknownFields,
+    }
+
+    #[derive(Deserialize, Debug, Default)]
+    struct Outer {
+        inner: Vec<Inner>,
+        #[serde(default)]
+        count: u64,
+        #[serde(rename = "$unknown", default)]
+        unknown: UnknownFields,
+    }
+
+    const BODY: &[u8] = br#"{
+        "inner": [{"name": "a", "owner": "x"}, {"name": "b", "website": "b.io"}],
+        "next": null
+    }"#;
+
+    #[test]
+    fn unknown_fields_are_kept_on_request() {
+        let outer: Outer = from_slice_keeping_unknown(BODY).unwrap();
+        assert_eq!(outer.unknown.get("next"), Some(&Value::Null));
+        assert!(outer.inner[0].unknown.is_empty());
+        assert_eq!(
+            outer.inner[1].unknown.get("website"),
+            Some(&Value::from("b.io"))
+        );
+        assert_eq!(outer.inner[1].name, "b");
+        assert_eq!(outer.inner[0].owner, "x");
+        assert_eq!(outer.count, 0);
+
+        let plain: Outer = serde_json::from_slice(BODY).unwrap();
+        assert!(plain.unknown.is_empty());
+        assert!(plain.inner[1].unknown.is_empty());
+    }
+
+    #[test]
+    fn drift_report_counts_new_and_missing_fields() {
+        let mut report = DriftReport::new();
+        report.record::<Outer>(BODY).unwrap();
+        report
+            .record::<Outer>(br#"{"inner": [{"name": "c", "owner": "y"}], "count": 1}"#)
+            .unwrap();
+        assert!(report.record::<Outer>(br#"{"inner": 5}"#).is_err());
+
+        assert_eq!(report.responses, 2);
+        let inner = report.get("Inner").unwrap();
+        assert_eq!(inner.seen, 3);
+        assert_eq!(inner.new_fields.get("website"), Some(&1));
+        assert_eq!(inner.missing_fields.get("owner"), Some(&1));
+        let outer = report.get("Outer").unwrap();
+        assert_eq!(outer.new_fields.get("next"), Some(&1));
+        assert_eq!(outer.missing_fields.get("count"), Some(&1));
+        assert!(report.has_drift());
+        assert_eq!(
+            report.to_string(),
+            "Inner (3 seen): new website (1); missing owner (1);\n\
+             Outer (2 seen): new next (1); missing count (1);\n"
+        );
+    }
+}
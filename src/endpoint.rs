@@ -0,0 +1,428 @@
+use crate::address::{Address, TxHash};
+use crate::config::ClientConfig;
+use crate::types::{
+    AddressInfo, AddressTransactions, CreatePoolParams, CreatedPool, GetAddressHistoryParams,
+    GetAddressInfoParams, GetAddressTransactionsParams, GetTokenHistoryParams, GetTopParams,
+    LastBlock, PoolAddresses, PoolAddressesParams, PoolChange, PoolLastOperations,
+    PoolLastTransactions, PoolUpdates, RequestConfig, TokenDailyPriceHistory,
+    TokenDailyTransactionCounts, TokenHistory, TokenInfo, TokensNew, Top, TopTokenHolders,
+    TopTokens, TxInfo,
+};
+use crate::{
+    add_pool_addresses_config, clear_pool_addresses_config, create_pool_config,
+    delete_pool_addresses_config, delete_pool_config, get_address_history_config,
+    get_address_info_config, get_address_transactions_config, get_last_block_config,
+    get_pool_addresses_config, get_pool_last_operations_config, get_pool_last_transactions_config,
+    get_pool_updates_config, get_token_daily_price_history_config,
+    get_token_daily_transaction_count_config, get_token_history_config, get_token_info_config,
+    get_tokens_new_config, get_top_config, get_top_token_holders_config, get_top_tokens_config,
+    get_tx_info_config,
+};
+use serde::de::DeserializeOwned;
+
+/// A request against one Ethplorer route, tied to the type its response parses into.
+pub trait Endpoint {
+    type Response: DeserializeOwned;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetAddressInfo {
+    pub address: Address,
+    pub params: GetAddressInfoParams,
+}
+
+impl Endpoint for GetAddressInfo {
+    type Response = AddressInfo;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_address_info_config(config, &self.address, &self.params)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTokenInfo {
+    pub address: Address,
+}
+
+impl Endpoint for GetTokenInfo {
+    type Response = TokenInfo;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_token_info_config(config, &self.address)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTopTokenHolders {
+    pub address: Address,
+    pub limit: u64,
+}
+
+impl Endpoint for GetTopTokenHolders {
+    type Response = TopTokenHolders;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_top_token_holders_config(config, &self.address, self.limit)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetLastBlock;
+
+impl Endpoint for GetLastBlock {
+    type Response = LastBlock;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_last_block_config(config)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTokensNew;
+
+impl Endpoint for GetTokensNew {
+    type Response = TokensNew;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_tokens_new_config(config)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTokenDailyTransactionCount {
+    pub address: Address,
+    pub period: u64,
+}
+
+impl Endpoint for GetTokenDailyTransactionCount {
+    type Response = TokenDailyTransactionCounts;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_token_daily_transaction_count_config(config, &self.address, self.period)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTokenHistory {
+    pub address: Address,
+    pub params: GetTokenHistoryParams,
+}
+
+impl Endpoint for GetTokenHistory {
+    type Response = TokenHistory;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_token_history_config(config, &self.address, &self.params)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetAddressHistory {
+    pub address: Address,
+    pub params: GetAddressHistoryParams,
+}
+
+impl Endpoint for GetAddressHistory {
+    type Response = TokenHistory;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_address_history_config(config, &self.address, &self.params)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetAddressTransactions {
+    pub address: Address,
+    pub params: GetAddressTransactionsParams,
+}
+
+impl Endpoint for GetAddressTransactions {
+    type Response = AddressTransactions;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_address_transactions_config(config, &self.address, &self.params)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTopTokens;
+
+impl Endpoint for GetTopTokens {
+    type Response = TopTokens;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_top_tokens_config(config)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTop {
+    pub params: GetTopParams,
+}
+
+impl Endpoint for GetTop {
+    type Response = Top;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_top_config(config, &self.params)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTokenDailyPriceHistory {
+    pub address: Address,
+    pub period: u64,
+}
+
+impl Endpoint for GetTokenDailyPriceHistory {
+    type Response = TokenDailyPriceHistory;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_token_daily_price_history_config(config, &self.address, self.period)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTxInfo {
+    pub hash: TxHash,
+}
+
+impl Endpoint for GetTxInfo {
+    type Response = TxInfo;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_tx_info_config(config, &self.hash)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CreatePool {
+    pub params: CreatePoolParams,
+}
+
+impl Endpoint for CreatePool {
+    type Response = CreatedPool;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        create_pool_config(config, &self.params)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeletePool {
+    pub pool_id: String,
+}
+
+impl Endpoint for DeletePool {
+    type Response = PoolChange;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        delete_pool_config(config, &self.pool_id)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AddPoolAddresses {
+    pub params: PoolAddressesParams,
+}
+
+impl Endpoint for AddPoolAddresses {
+    type Response = PoolChange;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        add_pool_addresses_config(config, &self.params)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeletePoolAddresses {
+    pub params: PoolAddressesParams,
+}
+
+impl Endpoint for DeletePoolAddresses {
+    type Response = PoolChange;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        delete_pool_addresses_config(config, &self.params)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClearPoolAddresses {
+    pub pool_id: String,
+}
+
+impl Endpoint for ClearPoolAddresses {
+    type Response = PoolChange;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        clear_pool_addresses_config(config, &self.pool_id)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetPoolAddresses {
+    pub pool_id: String,
+}
+
+impl Endpoint for GetPoolAddresses {
+    type Response = PoolAddresses;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_pool_addresses_config(config, &self.pool_id)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetPoolLastOperations {
+    pub pool_id: String,
+    pub period: u64,
+}
+
+impl Endpoint for GetPoolLastOperations {
+    type Response = PoolLastOperations;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_pool_last_operations_config(config, &self.pool_id, self.period)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetPoolLastTransactions {
+    pub pool_id: String,
+    pub period: u64,
+}
+
+impl Endpoint for GetPoolLastTransactions {
+    type Response = PoolLastTransactions;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_pool_last_transactions_config(config, &self.pool_id, self.period)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetPoolUpdates {
+    pub pool_id: String,
+    pub period: u64,
+}
+
+impl Endpoint for GetPoolUpdates {
+    type Response = PoolUpdates;
+
+    fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        get_pool_updates_config(config, &self.pool_id, self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Client, HttpResponse, MemoryTransport};
+
+    const ADDRESS: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+    const HOLDER: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+    #[test]
+    fn endpoint_config_matches_builder() {
+        let config = ClientConfig::new().with_api_key("key");
+        let endpoint = GetTopTokenHolders {
+            address: ADDRESS.parse().unwrap(),
+            limit: 100,
+        };
+        assert_eq!(
+            endpoint.to_config(&config),
+            get_top_token_holders_config(&config, &endpoint.address, 100)
+        );
+
+        let endpoint = GetTokenDailyPriceHistory {
+            address: ADDRESS.parse().unwrap(),
+            period: 30,
+        };
+        assert_eq!(
+            endpoint.to_config(&config),
+            get_token_daily_price_history_config(&config, &endpoint.address, 30)
+        );
+    }
+
+    #[test]
+    fn client_call_works() {
+        let client = Client::new(MemoryTransport::new().with_response(
+            &format!("https://api.ethplorer.io/getTopTokenHolders/{ADDRESS}"),
+            HttpResponse::new(
+                200,
+                format!(r#"{{"holders":[{{"address":"{HOLDER}","balance":5.0,"share":50.0}}]}}"#),
+            ),
+        ))
+        .with_config(ClientConfig::new().with_api_key("key"));
+
+        let holders = client
+            .call(GetTopTokenHolders {
+                address: ADDRESS.parse().unwrap(),
+                limit: 1,
+            })
+            .unwrap();
+        assert_eq!(holders.holders.len(), 1);
+        assert_eq!(holders.holders[0].address.to_string(), HOLDER);
+
+        let sent = client.transport().requests();
+        assert_eq!(sent[0].params[0], ("apiKey".to_string(), "key".to_string()));
+    }
+
+    #[test]
+    fn client_pool_calls_work() {
+        let transport = MemoryTransport::new()
+            .with_response(
+                "https://api-mon.ethplorer.io/createPool",
+                HttpResponse::new(200, r#"{"poolId":"pool-1"}"#),
+            )
+            .with_response(
+                "https://api-mon.ethplorer.io/deletePoolAddresses",
+                HttpResponse::new(200, r#"{"deleted":true}"#),
+            )
+            .with_response(
+                "https://api-mon.ethplorer.io/getPoolLastTransactions/pool-1",
+                HttpResponse::new(
+                    200,
+                    format!(
+                        r#"{{"{}":[{{"timestamp":1,"from":"{0}","to":"{}","hash":"0x{}","value":1.5,"input":"0x","success":true}}]}}"#,
+                        ADDRESS,
+                        HOLDER,
+                        "ab".repeat(32)
+                    ),
+                ),
+            );
+        let client = Client::new(transport);
+
+        let pool = client
+            .call(CreatePool {
+                params: CreatePoolParams {
+                    addresses: vec![ADDRESS.parse().unwrap()],
+                },
+            })
+            .unwrap();
+        assert_eq!(pool.pool_id, "pool-1");
+
+        let change = client
+            .call(DeletePoolAddresses {
+                params: PoolAddressesParams {
+                    pool_id: pool.pool_id.clone(),
+                    addresses: vec![ADDRESS.parse().unwrap()],
+                },
+            })
+            .unwrap();
+        assert!(change.success());
+
+        let transactions = client
+            .call(GetPoolLastTransactions {
+                pool_id: pool.pool_id,
+                period: 3600,
+            })
+            .unwrap();
+        let address = ADDRESS.parse().unwrap();
+        assert_eq!(transactions[&address].len(), 1);
+        assert_eq!(transactions[&address][0].to.to_string(), HOLDER);
+    }
+}
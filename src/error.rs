@@ -0,0 +1,178 @@
+use crate::config::LimitViolation;
+use crate::transport::TransportError;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum EthplorerError {
+    Transport(TransportError),
+    Status {
+        status: u16,
+        body: String,
+    },
+    Api(ApiError),
+    Deserialize {
+        source: serde_json::Error,
+        body: String,
+    },
+    // Params past the plan's limits under `LimitPolicy::Reject`
+    InvalidParams(Vec<LimitViolation>),
+    // The last error of a request that was retried, or given up on because the
+    // server's `retry_after` asked for a longer wait than the policy's `max_delay`
+    Retried {
+        attempts: u32,
+        last: Box<EthplorerError>,
+        retry_after: Option<Duration>,
+    },
+    // A full page of rows shares one timestamp, which the paging cursor can't get
+    // past without skipping rows
+    PageStalled {
+        timestamp: i64,
+        rows: usize,
+    },
+}
+
+impl Display for EthplorerError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            EthplorerError::Transport(err) => write!(f, "transport error: {err}"),
+            EthplorerError::Status { status, body } => {
+                write!(f, "unexpected status {status}: {body}")
+            }
+            EthplorerError::Api(err) => write!(f, "api error: {err}"),
+            EthplorerError::Deserialize { source, body } => {
+                write!(f, "failed to deserialize response ({source}): {body}")
+            }
+            EthplorerError::InvalidParams(violations) => {
+                let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+                write!(f, "invalid params: {}", violations.join(", "))
+            }
+            EthplorerError::Retried {
+                attempts,
+                last,
+                retry_after: None,
+            } => write!(f, "{last} (after {attempts} attempts)"),
+            EthplorerError::Retried {
+                attempts,
+                last,
+                retry_after: Some(retry_after),
+            } => write!(
+                f,
+                "{last} (after {attempts} attempts; the server asked to retry after {}s)",
+                retry_after.as_secs()
+            ),
+            EthplorerError::PageStalled { timestamp, rows } => write!(
+                f,
+                "a full page of {rows} rows shares timestamp {timestamp}; paging past it would skip rows"
+            ),
+        }
+    }
+}
+
+impl Error for EthplorerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EthplorerError::Transport(err) => Some(err),
+            EthplorerError::Api(err) => Some(err),
+            EthplorerError::Deserialize { source, .. } => Some(source),
+            EthplorerError::Retried { last, .. } => Some(last.as_ref()),
+            EthplorerError::Status { .. }
+            | EthplorerError::InvalidParams(_)
+            | EthplorerError::PageStalled { .. } => None,
+        }
+    }
+}
+
+impl From<TransportError> for EthplorerError {
+    fn from(err: TransportError) -> Self {
+        EthplorerError::Transport(err)
+    }
+}
+
+impl From<ApiError> for EthplorerError {
+    fn from(err: ApiError) -> Self {
+        EthplorerError::Api(err)
+    }
+}
+
+// Error codes documented by the Ethplorer API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorCode {
+    InvalidApiKey,
+    LimitExceeded,
+    InvalidTransactionHash,
+    InvalidAddress,
+    NotTokenContract,
+    NotFound,
+    Other(i64),
+}
+
+impl From<i64> for ApiErrorCode {
+    fn from(code: i64) -> Self {
+        match code {
+            1 => ApiErrorCode::InvalidApiKey,
+            3 => ApiErrorCode::LimitExceeded,
+            102 => ApiErrorCode::InvalidTransactionHash,
+            104 => ApiErrorCode::InvalidAddress,
+            150 => ApiErrorCode::NotTokenContract,
+            404 => ApiErrorCode::NotFound,
+            other => ApiErrorCode::Other(other),
+        }
+    }
+}
+
+impl From<ApiErrorCode> for i64 {
+    fn from(code: ApiErrorCode) -> Self {
+        match code {
+            ApiErrorCode::InvalidApiKey => 1,
+            ApiErrorCode::LimitExceeded => 3,
+            ApiErrorCode::InvalidTransactionHash => 102,
+            ApiErrorCode::InvalidAddress => 104,
+            ApiErrorCode::NotTokenContract => 150,
+            ApiErrorCode::NotFound => 404,
+            ApiErrorCode::Other(other) => other,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ApiErrorCode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        i64::deserialize(deserializer).map(ApiErrorCode::from)
+    }
+}
+
+// Body of Ethplorer's `{"error":{"code":..,"message":..}}` envelope
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ApiError {
+    pub code: ApiErrorCode,
+    #[serde(default)]
+    pub message: String,
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} (code {})", self.message, i64::from(self.code))
+    }
+}
+
+impl Error for ApiError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_error_code_mapping_works() {
+        for code in &[1, 3, 102, 104, 150, 404, 999] {
+            assert_eq!(i64::from(ApiErrorCode::from(*code)), *code);
+        }
+        assert_eq!(ApiErrorCode::from(104), ApiErrorCode::InvalidAddress);
+        assert_eq!(ApiErrorCode::from(7), ApiErrorCode::Other(7));
+    }
+}
@@ -1,13 +1,40 @@
 #![warn(clippy::all, clippy::pedantic)]
-#![feature(in_band_lifetimes)]
 
+pub use crate::address::*;
+pub use crate::amount::*;
+pub use crate::cache::*;
+pub use crate::client::*;
+pub use crate::config::*;
 pub use crate::consts::*;
+pub use crate::drift::*;
+pub use crate::endpoint::*;
+pub use crate::error::*;
+pub use crate::paginate::*;
+pub use crate::parse::*;
+pub use crate::rate_limit::*;
+pub use crate::retry::*;
+pub use crate::strictness::*;
+pub use crate::transport::*;
 pub use crate::types::*;
 
+pub mod address;
+pub mod amount;
+mod batch;
+pub mod cache;
+pub mod client;
+pub mod config;
 pub mod consts;
+pub mod drift;
+pub mod endpoint;
+pub mod error;
+pub mod paginate;
+pub mod parse;
+pub mod rate_limit;
+pub mod retry;
+pub mod strictness;
+pub mod transport;
 pub mod types;
 
-// TODO: Add status code error handling
 // TODO: use macro for repeat values
 
 #[must_use]
@@ -23,121 +50,139 @@ pub fn api_key_param(api_key: &str) -> (String, String) {
 // Get Address Info
 #[must_use]
 pub fn get_address_info_config(
-    api_key: &str,
-    address: &str,
+    config: &ClientConfig,
+    address: &Address,
     in_params: &GetAddressInfoParams,
 ) -> RequestConfig {
-    let key = api_key_param(api_key);
+    let key = config.api_key_param();
     let mut params = vec![key];
 
-    if !in_params.token.is_empty() {
-        params.push(("token".to_string(), in_params.token.clone()));
+    if let Some(token) = &in_params.token {
+        params.push(("token".to_string(), token.to_string()));
     }
 
     let eth_totals = in_params.show_eth_totals.to_string();
     params.push(("showETHTotals".to_string(), eth_totals));
 
+    if in_params.show_txs_count {
+        params.push(("showTxsCount".to_string(), "true".to_string()));
+    }
+
+    params.extend(in_params.extra_params.iter().cloned());
+
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config.network_for(GET_ADDRESS_INFO_ROUTE).to_string(),
         routes: vec![GET_ADDRESS_INFO_ROUTE.to_string(), address.to_string()],
         params,
+        method: Method::Get,
     }
 }
 
 // Get Token Info
 #[must_use]
-pub fn get_token_info_config(api_key: &str, address: &str) -> RequestConfig {
-    let key = api_key_param(api_key);
+pub fn get_token_info_config(config: &ClientConfig, address: &Address) -> RequestConfig {
+    let key = config.api_key_param();
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config.network_for(GET_TOKEN_INFO_ROUTE).to_string(),
         routes: vec![GET_TOKEN_INFO_ROUTE.to_string(), address.to_string()],
         params: vec![key],
+        method: Method::Get,
     }
 }
 
 // Get Top Token Holders
 #[must_use]
-pub fn get_top_token_holders_config(api_key: &str, address: &str, mut limit: u64) -> RequestConfig {
-    let key = api_key_param(api_key);
+pub fn get_top_token_holders_config(
+    config: &ClientConfig,
+    address: &Address,
+    mut limit: u64,
+) -> RequestConfig {
+    let key = config.api_key_param();
     let mut params = vec![key];
 
     if limit != 0 {
-        limit = limit.clamp(0, 1000);
+        limit = config.apply_limit(limit);
         params.push(("limit".to_string(), limit.to_string()));
     }
 
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config.network_for(GET_TOP_TOKEN_HOLDERS_ROUTE).to_string(),
         routes: vec![GET_TOP_TOKEN_HOLDERS_ROUTE.to_string(), address.to_string()],
         params,
+        method: Method::Get,
     }
 }
 
 // Get Last Block
 #[must_use]
-pub fn get_last_block_config(api_key: &str) -> RequestConfig {
-    let key = api_key_param(api_key);
+pub fn get_last_block_config(config: &ClientConfig) -> RequestConfig {
+    let key = config.api_key_param();
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config.network_for(GET_LAST_BLOCK_ROUTE).to_string(),
         routes: vec![GET_LAST_BLOCK_ROUTE.to_string()],
         params: vec![key],
+        method: Method::Get,
     }
 }
 
 // Get Token New
 #[must_use]
-pub fn get_tokens_new_config(api_key: &str) -> RequestConfig {
-    let key = api_key_param(api_key);
+pub fn get_tokens_new_config(config: &ClientConfig) -> RequestConfig {
+    let key = config.api_key_param();
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config.network_for(GET_TOKENS_NEW_ROUTE).to_string(),
         routes: vec![GET_TOKENS_NEW_ROUTE.to_string()],
         params: vec![key],
+        method: Method::Get,
     }
 }
 
 // Get Token Daily Transaction Count
 #[must_use]
 pub fn get_token_daily_transaction_count_config(
-    api_key: &str,
-    address: &str,
+    config: &ClientConfig,
+    address: &Address,
     mut period: u64,
 ) -> RequestConfig {
-    let key = api_key_param(api_key);
+    let key = config.api_key_param();
     let mut params = vec![key];
 
     if period != 0 {
-        period = period.clamp(0, 90);
+        period = config.apply_period(period);
         params.push(("period".to_string(), period.to_string()));
     }
 
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config
+            .network_for(GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE)
+            .to_string(),
         routes: vec![
             GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE.to_string(),
             address.to_string(),
         ],
         params,
+        method: Method::Get,
     }
 }
 
 // Get Token History
 #[must_use]
 pub fn get_token_history_config(
-    api_key: &str,
-    address: &str,
+    config: &ClientConfig,
+    address: &Address,
     in_params: &GetTokenHistoryParams,
 ) -> RequestConfig {
-    let key = api_key_param(api_key);
+    let key = config.api_key_param();
     let mut params = vec![key];
 
     let mut limit = in_params.limit;
     if limit != 0 {
-        limit = limit.clamp(0, 1000);
+        limit = config.apply_limit(limit);
         params.push(("limit".to_string(), limit.to_string()));
     }
 
-    if !in_params.history_type.is_empty() {
-        params.push(("type".to_string(), in_params.history_type.clone()));
+    if let Some(history_type) = &in_params.history_type {
+        params.push(("type".to_string(), history_type.to_string()));
     }
 
     let timestamp = in_params.timestamp.timestamp();
@@ -145,31 +190,34 @@ pub fn get_token_history_config(
         params.push(("timestamp".to_string(), timestamp.to_string()));
     }
 
+    params.extend(in_params.extra_params.iter().cloned());
+
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config.network_for(GET_TOKEN_HISTORY_ROUTE).to_string(),
         routes: vec![GET_TOKEN_HISTORY_ROUTE.to_string(), address.to_string()],
         params,
+        method: Method::Get,
     }
 }
 
 // Get Address History
 #[must_use]
 pub fn get_address_history_config(
-    api_key: &str,
-    address: &str,
+    config: &ClientConfig,
+    address: &Address,
     in_params: &GetAddressHistoryParams,
 ) -> RequestConfig {
-    let key = api_key_param(api_key);
+    let key = config.api_key_param();
     let mut params = vec![key];
 
     let mut limit = in_params.limit;
     if limit != 0 {
-        limit = limit.clamp(0, 1000);
+        limit = config.apply_limit(limit);
         params.push(("limit".to_string(), limit.to_string()));
     }
 
-    if !in_params.history_type.is_empty() {
-        params.push(("type".to_string(), in_params.history_type.clone()));
+    if let Some(history_type) = &in_params.history_type {
+        params.push(("type".to_string(), history_type.to_string()));
     }
 
     let timestamp = in_params.timestamp.timestamp();
@@ -177,30 +225,33 @@ pub fn get_address_history_config(
         params.push(("timestamp".to_string(), timestamp.to_string()));
     }
 
-    if !in_params.token.is_empty() {
-        params.push(("token".to_string(), in_params.token.clone()));
+    if let Some(token) = &in_params.token {
+        params.push(("token".to_string(), token.to_string()));
     }
 
+    params.extend(in_params.extra_params.iter().cloned());
+
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config.network_for(GET_ADDRESS_HISTORY).to_string(),
         routes: vec![GET_ADDRESS_HISTORY.to_string(), address.to_string()],
         params,
+        method: Method::Get,
     }
 }
 
 // Get Address Transactions
 #[must_use]
 pub fn get_address_transactions_config(
-    api_key: &str,
-    address: &str,
+    config: &ClientConfig,
+    address: &Address,
     in_params: &GetAddressTransactionsParams,
 ) -> RequestConfig {
-    let key = api_key_param(api_key);
+    let key = config.api_key_param();
     let mut params = vec![key];
 
     let mut limit = in_params.limit;
     if limit != 0 {
-        limit = limit.clamp(0, 1000);
+        limit = config.apply_limit(limit);
         params.push(("limit".to_string(), limit.to_string()));
     }
 
@@ -212,79 +263,249 @@ pub fn get_address_transactions_config(
     let show_zero_values = in_params.show_zero_values.to_string();
     params.push(("showZeroValues".to_string(), show_zero_values));
 
+    params.extend(in_params.extra_params.iter().cloned());
+
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config
+            .network_for(GET_ADDRESS_TRANSACTIONS_ROUTE)
+            .to_string(),
         routes: vec![
             GET_ADDRESS_TRANSACTIONS_ROUTE.to_string(),
             address.to_string(),
         ],
         params,
+        method: Method::Get,
     }
 }
 
 // Get Top Tokens
 #[must_use]
-pub fn get_top_tokens_config(api_key: &str) -> RequestConfig {
-    let key = api_key_param(api_key);
+pub fn get_top_tokens_config(config: &ClientConfig) -> RequestConfig {
+    let key = config.api_key_param();
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config.network_for(GET_TOP_TOKENS_ROUTE).to_string(),
         routes: vec![GET_TOP_TOKENS_ROUTE.to_string()],
         params: vec![key],
+        method: Method::Get,
     }
 }
 
 // Get Top
 #[must_use]
-pub fn get_top_config(api_key: &str, in_params: &GetTopParams) -> RequestConfig {
-    let key = api_key_param(api_key);
+pub fn get_top_config(config: &ClientConfig, in_params: &GetTopParams) -> RequestConfig {
+    let key = config.api_key_param();
     let mut params = vec![key];
 
     let mut limit = in_params.limit;
     if limit != 0 {
-        limit = limit.clamp(0, 1000);
+        limit = config.apply_limit(limit);
         params.push(("limit".to_string(), limit.to_string()));
     }
 
-    if !in_params.criteria.is_empty() {
-        params.push(("criteria".to_string(), in_params.criteria.clone()));
+    if let Some(criteria) = &in_params.criteria {
+        params.push(("criteria".to_string(), criteria.to_string()));
     }
 
+    params.extend(in_params.extra_params.iter().cloned());
+
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config.network_for(GET_TOP_ROUTE).to_string(),
         routes: vec![GET_TOP_ROUTE.to_string()],
         params,
+        method: Method::Get,
+    }
+}
+
+// Get Tx Info
+#[must_use]
+pub fn get_tx_info_config(config: &ClientConfig, hash: &TxHash) -> RequestConfig {
+    let key = config.api_key_param();
+    RequestConfig {
+        network: config.network_for(GET_TX_INFO_ROUTE).to_string(),
+        routes: vec![GET_TX_INFO_ROUTE.to_string(), hash.to_string()],
+        params: vec![key],
+        method: Method::Get,
     }
 }
 
 // Get Token Daily Price History
 #[must_use]
 pub fn get_token_daily_price_history_config(
-    api_key: &str,
-    address: &str,
+    config: &ClientConfig,
+    address: &Address,
     mut period: u64,
 ) -> RequestConfig {
-    let key = api_key_param(api_key);
+    let key = config.api_key_param();
     let mut params = vec![key];
 
     if period != 0 {
-        period = period.clamp(0, 90);
+        period = config.apply_period(period);
         params.push(("period".to_string(), period.to_string()));
     }
 
     RequestConfig {
-        network: NETWORK.to_string(),
+        network: config
+            .network_for(GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE)
+            .to_string(),
         routes: vec![
             GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE.to_string(),
             address.to_string(),
         ],
         params,
+        method: Method::Get,
+    }
+}
+
+// The pool routes take a comma separated address list
+fn addresses_param(addresses: &[Address]) -> (String, String) {
+    let joined: Vec<String> = addresses.iter().map(ToString::to_string).collect();
+    ("addresses".to_string(), joined.join(","))
+}
+
+fn pool_action_config(
+    config: &ClientConfig,
+    route: &str,
+    pool_id: &str,
+    addresses: Option<&[Address]>,
+) -> RequestConfig {
+    let key = config.api_key_param();
+    let mut params = vec![key, ("poolId".to_string(), pool_id.to_string())];
+
+    if let Some(addresses) = addresses {
+        params.push(addresses_param(addresses));
+    }
+
+    RequestConfig {
+        network: config.monitor_network_for(route).to_string(),
+        routes: vec![route.to_string()],
+        params,
+        method: Method::Post,
+    }
+}
+
+fn pool_getter_config(
+    config: &ClientConfig,
+    route: &str,
+    pool_id: &str,
+    period: u64,
+) -> RequestConfig {
+    let key = config.api_key_param();
+    let mut params = vec![key];
+
+    // Seconds rather than days on the pool routes
+    if period != 0 {
+        params.push(("period".to_string(), period.to_string()));
+    }
+
+    RequestConfig {
+        network: config.monitor_network_for(route).to_string(),
+        routes: vec![route.to_string(), pool_id.to_string()],
+        params,
+        method: Method::Get,
+    }
+}
+
+// Create Pool
+#[must_use]
+pub fn create_pool_config(config: &ClientConfig, in_params: &CreatePoolParams) -> RequestConfig {
+    let key = config.api_key_param();
+    let mut params = vec![key];
+
+    if !in_params.addresses.is_empty() {
+        params.push(addresses_param(&in_params.addresses));
+    }
+
+    RequestConfig {
+        network: config.monitor_network_for(CREATE_POOL_ROUTE).to_string(),
+        routes: vec![CREATE_POOL_ROUTE.to_string()],
+        params,
+        method: Method::Post,
     }
 }
 
+// Delete Pool
+#[must_use]
+pub fn delete_pool_config(config: &ClientConfig, pool_id: &str) -> RequestConfig {
+    pool_action_config(config, DELETE_POOL_ROUTE, pool_id, None)
+}
+
+// Add Pool Addresses
+#[must_use]
+pub fn add_pool_addresses_config(
+    config: &ClientConfig,
+    in_params: &PoolAddressesParams,
+) -> RequestConfig {
+    pool_action_config(
+        config,
+        ADD_POOL_ADDRESSES_ROUTE,
+        &in_params.pool_id,
+        Some(&in_params.addresses),
+    )
+}
+
+// Delete Pool Addresses
+#[must_use]
+pub fn delete_pool_addresses_config(
+    config: &ClientConfig,
+    in_params: &PoolAddressesParams,
+) -> RequestConfig {
+    pool_action_config(
+        config,
+        DELETE_POOL_ADDRESSES_ROUTE,
+        &in_params.pool_id,
+        Some(&in_params.addresses),
+    )
+}
+
+// Clear Pool Addresses
+#[must_use]
+pub fn clear_pool_addresses_config(config: &ClientConfig, pool_id: &str) -> RequestConfig {
+    pool_action_config(config, CLEAR_POOL_ADDRESSES_ROUTE, pool_id, None)
+}
+
+// Get Pool Addresses
+#[must_use]
+pub fn get_pool_addresses_config(config: &ClientConfig, pool_id: &str) -> RequestConfig {
+    pool_getter_config(config, GET_POOL_ADDRESSES_ROUTE, pool_id, 0)
+}
+
+// Get Pool Last Operations
+#[must_use]
+pub fn get_pool_last_operations_config(
+    config: &ClientConfig,
+    pool_id: &str,
+    period: u64,
+) -> RequestConfig {
+    pool_getter_config(config, GET_POOL_LAST_OPERATIONS_ROUTE, pool_id, period)
+}
+
+// Get Pool Last Transactions
+#[must_use]
+pub fn get_pool_last_transactions_config(
+    config: &ClientConfig,
+    pool_id: &str,
+    period: u64,
+) -> RequestConfig {
+    pool_getter_config(config, GET_POOL_LAST_TRANSACTIONS_ROUTE, pool_id, period)
+}
+
+// Get Pool Updates
+#[must_use]
+pub fn get_pool_updates_config(config: &ClientConfig, pool_id: &str, period: u64) -> RequestConfig {
+    pool_getter_config(config, GET_POOL_UPDATES_ROUTE, pool_id, period)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const ADDRESS: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+    const TX_HASH: &str = "0x1c0e8a3cd8b8d22a8ee0bbc1e5e7c3e10b3df0ff2b4e6bc1cc0a3a1e1d9b2f3a";
+
+    fn address() -> Address {
+        ADDRESS.parse().unwrap()
+    }
+
     #[test]
     fn api_key_param_works() {
         let key1 = api_key_param("");
@@ -297,49 +518,134 @@ mod tests {
     #[test]
     fn get_address_info_config_works() {
         let config = get_address_info_config(
-            "",
-            "0x0",
+            &ClientConfig::default(),
+            &address(),
             &GetAddressInfoParams {
-                token: "token".to_string(),
+                token: Some(address()),
                 show_eth_totals: false,
+                show_txs_count: false,
+                extra_params: vec![],
             },
         );
         assert_eq!(config.network, NETWORK);
-        assert_eq!(config.routes, vec![GET_ADDRESS_INFO_ROUTE, "0x0"]);
+        assert_eq!(config.routes, vec![GET_ADDRESS_INFO_ROUTE, ADDRESS]);
         assert_eq!(
             config.params,
             vec![
                 ("apiKey".to_string(), "freekey".to_string()),
-                ("token".to_string(), "token".to_string()),
+                ("token".to_string(), ADDRESS.to_string()),
                 ("showETHTotals".to_string(), "false".to_string()),
             ]
         );
         assert_eq!(
             config.to_string(),
-            "https://api.ethplorer.io/getAddressInfo/0x0"
+            "https://api.ethplorer.io/getAddressInfo/0xdac17f958d2ee523a2206206994597c13d831ec7"
+        );
+    }
+
+    #[test]
+    fn extra_params_are_appended() {
+        let config = get_address_info_config(
+            &ClientConfig::default(),
+            &address(),
+            &GetAddressInfoParams {
+                show_txs_count: true,
+                extra_params: vec![("showTokens".to_string(), "false".to_string())],
+                ..GetAddressInfoParams::default()
+            },
+        );
+        assert_eq!(
+            config.url(),
+            format!(
+                "https://api.ethplorer.io/getAddressInfo/{ADDRESS}?apiKey=freekey&showETHTotals=false&showTxsCount=true&showTokens=false"
+            )
+        );
+
+        let config = get_top_config(
+            &ClientConfig::default(),
+            &GetTopParams {
+                extra_params: vec![("period".to_string(), "7".to_string())],
+                ..GetTopParams::default()
+            },
+        );
+        assert_eq!(
+            config.params.last(),
+            Some(&("period".to_string(), "7".to_string()))
+        );
+    }
+
+    #[test]
+    fn request_config_url_works() {
+        let config = get_address_info_config(
+            &ClientConfig::new().with_api_key("secret"),
+            &address(),
+            &GetAddressInfoParams {
+                token: Some(address()),
+                show_eth_totals: true,
+                show_txs_count: false,
+                extra_params: vec![],
+            },
+        );
+        assert_eq!(
+            config.url(),
+            format!(
+                "https://api.ethplorer.io/getAddressInfo/{ADDRESS}?apiKey=secret&token={ADDRESS}&showETHTotals=true"
+            )
+        );
+        assert_eq!(
+            config.redacted_url(),
+            format!(
+                "https://api.ethplorer.io/getAddressInfo/{ADDRESS}?apiKey=***&token={ADDRESS}&showETHTotals=true"
+            )
+        );
+
+        let config = RequestConfig {
+            network: NETWORK.to_string(),
+            routes: vec![GET_TOKEN_INFO_ROUTE.to_string(), "0x0/..?".to_string()],
+            params: vec![("token".to_string(), "a b&c".to_string())],
+            method: Method::Get,
+        };
+        assert_eq!(
+            config.url(),
+            "https://api.ethplorer.io/getTokenInfo/0x0%2F..%3F?token=a%20b%26c"
+        );
+    }
+
+    #[test]
+    fn client_config_base_url_works() {
+        let config = ClientConfig::new()
+            .with_base_url("http://localhost:8080")
+            .with_route_base_url(GET_TOP_TOKENS_ROUTE, "http://cache");
+        assert_eq!(
+            get_token_info_config(&config, &address()).url(),
+            format!("http://localhost:8080/getTokenInfo/{ADDRESS}?apiKey=freekey")
+        );
+        assert_eq!(
+            get_top_tokens_config(&config).url(),
+            "http://cache/getTopTokens?apiKey=freekey"
         );
     }
 
     #[test]
     fn get_token_info_config_works() {
-        let config = get_token_info_config("", "0x0");
+        let config = get_token_info_config(&ClientConfig::default(), &address());
         assert_eq!(config.network, NETWORK);
-        assert_eq!(config.routes, vec![GET_TOKEN_INFO_ROUTE, "0x0"]);
+        assert_eq!(config.routes, vec![GET_TOKEN_INFO_ROUTE, ADDRESS]);
         assert_eq!(
             config.params,
             vec![("apiKey".to_string(), "freekey".to_string())]
         );
         assert_eq!(
             config.to_string(),
-            "https://api.ethplorer.io/getTokenInfo/0x0"
+            "https://api.ethplorer.io/getTokenInfo/0xdac17f958d2ee523a2206206994597c13d831ec7"
         );
     }
 
     #[test]
     fn get_top_token_holders_config_works() {
-        let config = get_top_token_holders_config("", "0x0", 100);
+        let config = get_top_token_holders_config(&ClientConfig::default(), &address(), 100);
         assert_eq!(config.network, NETWORK);
-        assert_eq!(config.routes, vec![GET_TOP_TOKEN_HOLDERS_ROUTE, "0x0"]);
+        assert_eq!(config.routes, vec![GET_TOP_TOKEN_HOLDERS_ROUTE, ADDRESS]);
         assert_eq!(
             config.params,
             vec![
@@ -349,13 +655,13 @@ mod tests {
         );
         assert_eq!(
             config.to_string(),
-            "https://api.ethplorer.io/getTopTokenHolders/0x0"
+            "https://api.ethplorer.io/getTopTokenHolders/0xdac17f958d2ee523a2206206994597c13d831ec7"
         );
     }
 
     #[test]
     fn get_last_block_config_works() {
-        let config = get_last_block_config("");
+        let config = get_last_block_config(&ClientConfig::default());
         assert_eq!(config.network, NETWORK);
         assert_eq!(config.routes, vec![GET_LAST_BLOCK_ROUTE]);
         assert_eq!(
@@ -367,7 +673,7 @@ mod tests {
 
     #[test]
     fn get_tokens_new_config_works() {
-        let config = get_tokens_new_config("");
+        let config = get_tokens_new_config(&ClientConfig::default());
         assert_eq!(config.network, NETWORK);
         assert_eq!(config.routes, vec![GET_TOKENS_NEW_ROUTE]);
         assert_eq!(
@@ -379,94 +685,98 @@ mod tests {
 
     #[test]
     fn get_token_daily_transaction_count_config_works() {
-        let config = get_token_daily_transaction_count_config("", "0x0", 50);
+        let config =
+            get_token_daily_transaction_count_config(&ClientConfig::default(), &address(), 50);
         assert_eq!(config.network, NETWORK);
         assert_eq!(
             config.routes,
-            vec![GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE, "0x0"]
+            vec![GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE, ADDRESS]
         );
         assert_eq!(
             config.params,
             vec![
                 ("apiKey".to_string(), "freekey".to_string()),
-                ("period".to_string(), "50".to_string()),
+                ("period".to_string(), "30".to_string()),
             ]
         );
         assert_eq!(
             config.to_string(),
-            "https://api.ethplorer.io/getTokenHistoryGrouped/0x0"
+            "https://api.ethplorer.io/getTokenHistoryGrouped/0xdac17f958d2ee523a2206206994597c13d831ec7"
         );
     }
 
     #[test]
     fn get_token_history_config_works() {
         let config = get_token_history_config(
-            "",
-            "0x0",
+            &ClientConfig::default(),
+            &address(),
             &GetTokenHistoryParams {
-                history_type: "type".to_string(),
+                history_type: Some(OperationType::Transfer),
                 limit: 50,
-                timestamp: Default::default(),
+                timestamp: Timestamp::default(),
+                extra_params: vec![],
             },
         );
         assert_eq!(config.network, NETWORK);
-        assert_eq!(config.routes, vec![GET_TOKEN_HISTORY_ROUTE, "0x0"]);
+        assert_eq!(config.routes, vec![GET_TOKEN_HISTORY_ROUTE, ADDRESS]);
         assert_eq!(
             config.params,
             vec![
                 ("apiKey".to_string(), "freekey".to_string()),
                 ("limit".to_string(), "50".to_string()),
-                ("type".to_string(), "type".to_string()),
+                ("type".to_string(), "transfer".to_string()),
             ]
         );
         assert_eq!(
             config.to_string(),
-            "https://api.ethplorer.io/getTokenHistory/0x0"
+            "https://api.ethplorer.io/getTokenHistory/0xdac17f958d2ee523a2206206994597c13d831ec7"
         );
     }
 
     #[test]
     fn get_address_history_config_works() {
         let config = get_address_history_config(
-            "",
-            "0x0",
+            &ClientConfig::default(),
+            &address(),
             &GetAddressHistoryParams {
-                history_type: "type".to_string(),
+                history_type: Some(OperationType::Transfer),
                 limit: 50,
-                timestamp: Default::default(),
-                token: "token".to_string(),
+                timestamp: Timestamp::default(),
+                token: Some(address()),
+                extra_params: vec![],
             },
         );
         assert_eq!(config.network, NETWORK);
-        assert_eq!(config.routes, vec![GET_ADDRESS_HISTORY, "0x0"]);
+        assert_eq!(config.routes, vec![GET_ADDRESS_HISTORY, ADDRESS]);
         assert_eq!(
             config.params,
             vec![
                 ("apiKey".to_string(), "freekey".to_string()),
                 ("limit".to_string(), "50".to_string()),
-                ("type".to_string(), "type".to_string()),
-                ("token".to_string(), "token".to_string()),
+                ("type".to_string(), "transfer".to_string()),
+                ("token".to_string(), ADDRESS.to_string()),
             ]
         );
         assert_eq!(
             config.to_string(),
-            "https://api.ethplorer.io/getAddressHistory/0x0"
+            "https://api.ethplorer.io/getAddressHistory/0xdac17f958d2ee523a2206206994597c13d831ec7"
         );
     }
 
     #[test]
     fn get_address_transactions_config_works() {
         let config = get_address_transactions_config(
-            "",
-            "0x0",
+            &ClientConfig::default(),
+            &address(),
             &GetAddressTransactionsParams {
                 limit: 50,
-                timestamp: Default::default(),
+                timestamp: Timestamp::default(),
                 show_zero_values: false,
+                extra_params: vec![],
             },
         );
         assert_eq!(config.network, NETWORK);
-        assert_eq!(config.routes, vec![GET_ADDRESS_TRANSACTIONS_ROUTE, "0x0"]);
+        assert_eq!(config.routes, vec![GET_ADDRESS_TRANSACTIONS_ROUTE, ADDRESS]);
         assert_eq!(
             config.params,
             vec![
@@ -477,13 +787,13 @@ mod tests {
         );
         assert_eq!(
             config.to_string(),
-            "https://api.ethplorer.io/getAddressTransactions/0x0"
+            "https://api.ethplorer.io/getAddressTransactions/0xdac17f958d2ee523a2206206994597c13d831ec7"
         );
     }
 
     #[test]
     fn get_top_tokens_config_works() {
-        let config = get_top_tokens_config("");
+        let config = get_top_tokens_config(&ClientConfig::default());
         assert_eq!(config.network, NETWORK);
         assert_eq!(config.routes, vec![GET_TOP_TOKENS_ROUTE]);
         assert_eq!(
@@ -496,10 +806,11 @@ mod tests {
     #[test]
     fn get_top_config_works() {
         let config = get_top_config(
-            "",
+            &ClientConfig::default(),
             &GetTopParams {
                 limit: 50,
-                criteria: "crit".to_string(),
+                criteria: Some(TopCriteria::Cap),
+                extra_params: vec![],
             },
         );
         assert_eq!(config.network, NETWORK);
@@ -509,7 +820,7 @@ mod tests {
             vec![
                 ("apiKey".to_string(), "freekey".to_string()),
                 ("limit".to_string(), "50".to_string()),
-                ("criteria".to_string(), "crit".to_string()),
+                ("criteria".to_string(), "cap".to_string()),
             ]
         );
         assert_eq!(config.to_string(), "https://api.ethplorer.io/getTop");
@@ -517,22 +828,203 @@ mod tests {
 
     #[test]
     fn get_token_daily_price_history_config_works() {
-        let config = get_token_daily_price_history_config("", "0x0", 50);
+        let config = get_token_daily_price_history_config(&ClientConfig::default(), &address(), 50);
         assert_eq!(config.network, NETWORK);
         assert_eq!(
             config.routes,
-            vec![GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE, "0x0"]
+            vec![GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE, ADDRESS]
         );
         assert_eq!(
             config.params,
             vec![
                 ("apiKey".to_string(), "freekey".to_string()),
-                ("period".to_string(), "50".to_string()),
+                ("period".to_string(), "30".to_string()),
             ]
         );
         assert_eq!(
             config.to_string(),
-            "https://api.ethplorer.io/getTokenPriceHistoryGrouped/0x0"
+            "https://api.ethplorer.io/getTokenPriceHistoryGrouped/0xdac17f958d2ee523a2206206994597c13d831ec7"
+        );
+    }
+
+    #[test]
+    fn get_tx_info_config_works() {
+        let hash: TxHash = TX_HASH.parse().unwrap();
+        let config = get_tx_info_config(&ClientConfig::default(), &hash);
+        assert_eq!(config.network, NETWORK);
+        assert_eq!(config.routes, vec![GET_TX_INFO_ROUTE, TX_HASH]);
+        assert_eq!(
+            config.params,
+            vec![("apiKey".to_string(), "freekey".to_string())]
+        );
+    }
+
+    #[test]
+    fn tx_info_deserialize_works() {
+        let body = format!(
+            r#"{{
+                "hash": "{hash}",
+                "timestamp": 1633046400,
+                "blockNumber": 13330000,
+                "confirmations": 120,
+                "success": true,
+                "from": "{holder}",
+                "to": "{token}",
+                "value": 0,
+                "input": "0xa9059cbb",
+                "gasLimit": 80000,
+                "gasUsed": 51379,
+                "gasPrice": "123456789012345678901",
+                "logs": [{{"address": "{token}", "topics": ["0xddf2"], "data": "0x01"}}],
+                "operations": [{{
+                    "timestamp": 1633046400,
+                    "transactionHash": "{hash}",
+                    "value": "1000000",
+                    "type": "transfer",
+                    "from": "{holder}",
+                    "to": "{token}",
+                    "tokenInfo": {{"address": "{token}", "name": "Tether USD", "symbol": "USDT", "decimals": "6"}}
+                }}]
+            }}"#,
+            hash = TX_HASH,
+            holder = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            token = ADDRESS,
+        );
+        let info: TxInfo = serde_json::from_str(&body).unwrap();
+        assert_eq!(info.hash.to_string(), TX_HASH);
+        assert_eq!(info.block_number, Some(13_330_000));
+        assert_eq!(info.to, Some(address()));
+        assert_eq!(info.gas_used, RawAmount::from(51379));
+        assert_eq!(info.gas_price.to_string(), "123456789012345678901");
+        assert_eq!(info.logs[0].topics, vec!["0xddf2"]);
+        assert_eq!(info.operations[0].transaction_hash, info.hash);
+        assert_eq!(info.operations[0].token_info.symbol, "USDT");
+        assert_eq!(info.creates, None);
+    }
+
+    #[test]
+    fn tokens_new_deserialize_works() {
+        let body = format!(
+            r#"[{{"address":"{ADDRESS}","name":"New","symbol":"NEW","decimals":"18","added":1633046400}},
+                {{"address":"{ADDRESS}","name":"Old","symbol":"OLD","decimals":"0"}}]"#
+        );
+        let tokens: TokensNew = serde_json::from_str(&body).unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].added_at().unwrap().timestamp(), 1_633_046_400);
+        assert_eq!(tokens[1].added_at(), None);
+        let symbols: Vec<String> = tokens.into_iter().map(|token| token.symbol).collect();
+        assert_eq!(symbols, vec!["NEW", "OLD"]);
+    }
+
+    #[test]
+    fn top_deserialize_works() {
+        let body = format!(
+            r#"{{
+                "tokens": [{{
+                    "address": "{ADDRESS}", "name": "Tether USD", "symbol": "USDT", "decimals": "6",
+                    "price": {{"rate": 1.0, "currency": "USD"}},
+                    "volume": 1500.5, "volumePrevious": 1200.25, "cap": 7.5e10
+                }}],
+                "totals": {{"tokens": 2500, "tokensWithPrice": 900, "volume24h": 1.5e9}}
+            }}"#
+        );
+        let top: Top = serde_json::from_str(&body).unwrap();
+        let token = &top.tokens[0];
+        assert_eq!(token.token_info.symbol, "USDT");
+        assert_eq!(token.token_info.decimals, 6);
+        assert!((token.token_info.price.rate().unwrap() - 1.0).abs() < f64::EPSILON);
+        assert!((token.volume - 1500.5).abs() < f64::EPSILON);
+        assert!((token.cap_previous).abs() < f64::EPSILON);
+        assert_eq!(top.totals.tokens_with_price, 900);
+
+        let top: Top = serde_json::from_str(r#"{"tokens":[]}"#).unwrap();
+        assert_eq!(top.totals.tokens, 0);
+    }
+
+    #[test]
+    fn address_transactions_deserialize_works() {
+        let body = format!(
+            r#"[{{"timestamp":1633046400,"from":"{ADDRESS}","to":"{ADDRESS}","hash":"{TX_HASH}","value":0.25,"input":"0x","success":false}}]"#
+        );
+        let transactions: AddressTransactions = serde_json::from_str(&body).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].hash.to_string(), TX_HASH);
+        assert!(!transactions[0].success);
+    }
+
+    #[test]
+    fn operation_type_works() {
+        let types: Vec<OperationType> =
+            serde_json::from_str(r#"["transfer", "burn", "swap"]"#).unwrap();
+        assert_eq!(
+            types,
+            vec![
+                OperationType::Transfer,
+                OperationType::Burn,
+                OperationType::Unknown("swap".to_string()),
+            ]
+        );
+        assert_eq!(types[2].to_string(), "swap");
+        assert_eq!(TopCriteria::from("trade"), TopCriteria::Trade);
+        assert_eq!(TopCriteria::Count.to_string(), "count");
+    }
+
+    #[test]
+    fn pool_action_configs_work() {
+        let config = create_pool_config(
+            &ClientConfig::default(),
+            &CreatePoolParams {
+                addresses: vec![address(), address()],
+            },
+        );
+        assert_eq!(config.network, MONITOR_NETWORK);
+        assert_eq!(config.method, Method::Post);
+        assert_eq!(config.url(), "https://api-mon.ethplorer.io/createPool");
+        assert_eq!(
+            config.form_body().unwrap(),
+            format!("apiKey=freekey&addresses={ADDRESS}%2C{ADDRESS}")
+        );
+
+        let config = add_pool_addresses_config(
+            &ClientConfig::new().with_api_key("secret"),
+            &PoolAddressesParams {
+                pool_id: "pool-1".to_string(),
+                addresses: vec![address()],
+            },
+        );
+        assert_eq!(config.routes, vec![ADD_POOL_ADDRESSES_ROUTE]);
+        assert_eq!(
+            config.params,
+            vec![
+                ("apiKey".to_string(), "secret".to_string()),
+                ("poolId".to_string(), "pool-1".to_string()),
+                ("addresses".to_string(), ADDRESS.to_string()),
+            ]
+        );
+        assert!(!config.fingerprint().contains("secret"));
+
+        let config = clear_pool_addresses_config(&ClientConfig::default(), "pool-1");
+        assert_eq!(config.form_body().unwrap(), "apiKey=freekey&poolId=pool-1");
+    }
+
+    #[test]
+    fn pool_getter_configs_work() {
+        let config = get_pool_last_operations_config(&ClientConfig::default(), "pool-1", 600);
+        assert_eq!(config.method, Method::Get);
+        assert_eq!(config.form_body(), None);
+        assert_eq!(
+            config.url(),
+            "https://api-mon.ethplorer.io/getPoolLastOperations/pool-1?apiKey=freekey&period=600"
+        );
+        assert!(ClientConfig::default().check_limits(&config).is_empty());
+
+        let config = get_pool_addresses_config(
+            &ClientConfig::new().with_monitor_base_url("http://localhost:8081"),
+            "pool-1",
+        );
+        assert_eq!(
+            config.url(),
+            "http://localhost:8081/getPoolAddresses/pool-1?apiKey=freekey"
         );
     }
 }
@@ -0,0 +1,533 @@
+use crate::address::{Address, TxHash};
+use crate::amount::RawAmount;
+#[cfg(feature = "async")]
+use crate::client::AsyncClient;
+use crate::client::Client;
+use crate::endpoint::{Endpoint, GetAddressHistory, GetAddressTransactions, GetTokenHistory};
+use crate::error::EthplorerError;
+#[cfg(feature = "async")]
+use crate::transport::AsyncTransport;
+use crate::transport::Transport;
+use crate::types::{AddressTransaction, OperationType, Operations, RequestConfig, Timestamp};
+use chrono::{TimeZone, Utc};
+use std::collections::{HashSet, VecDeque};
+use std::convert::TryFrom;
+use std::hash::Hash;
+
+/// An endpoint whose rows come newest first and can be walked backwards through
+/// its `timestamp` param.
+pub trait Paginated: Endpoint + Clone {
+    type Item;
+    type Key: Eq + Hash;
+
+    fn items(response: Self::Response) -> Vec<Self::Item>;
+
+    fn timestamp(item: &Self::Item) -> i64;
+
+    // Identifies a row across pages that share a boundary timestamp
+    fn key(item: &Self::Item) -> Self::Key;
+
+    // Starting point of the walk, if the caller set one
+    fn cursor(&self) -> Option<i64>;
+
+    #[must_use]
+    fn with_cursor(&self, cursor: Timestamp) -> Self;
+}
+
+type OperationKey = (TxHash, OperationType, Address, Address, Address, RawAmount);
+
+fn operation_key(item: &Operations) -> OperationKey {
+    (
+        item.transaction_hash,
+        item.op_type.clone(),
+        item.from,
+        item.to,
+        item.token_info.address,
+        item.value,
+    )
+}
+
+fn timestamp_cursor(timestamp: Timestamp) -> Option<i64> {
+    Some(timestamp.timestamp()).filter(|seconds| *seconds != 0)
+}
+
+// Rows per page as actually sent, which `LimitPolicy::Clamp` may have lowered; 0
+// when left to the API
+fn sent_limit(request: &RequestConfig) -> u64 {
+    request
+        .params
+        .iter()
+        .find(|(name, _)| name == "limit")
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0)
+}
+
+impl Paginated for GetAddressHistory {
+    type Item = Operations;
+    type Key = OperationKey;
+
+    fn items(response: Self::Response) -> Vec<Operations> {
+        response.operations
+    }
+
+    fn timestamp(item: &Operations) -> i64 {
+        item.timestamp.timestamp()
+    }
+
+    fn key(item: &Operations) -> OperationKey {
+        operation_key(item)
+    }
+
+    fn cursor(&self) -> Option<i64> {
+        timestamp_cursor(self.params.timestamp)
+    }
+
+    fn with_cursor(&self, cursor: Timestamp) -> Self {
+        let mut next = self.clone();
+        next.params.timestamp = cursor;
+        next
+    }
+}
+
+impl Paginated for GetTokenHistory {
+    type Item = Operations;
+    type Key = OperationKey;
+
+    fn items(response: Self::Response) -> Vec<Operations> {
+        response.operations
+    }
+
+    fn timestamp(item: &Operations) -> i64 {
+        item.timestamp.timestamp()
+    }
+
+    fn key(item: &Operations) -> OperationKey {
+        operation_key(item)
+    }
+
+    fn cursor(&self) -> Option<i64> {
+        timestamp_cursor(self.params.timestamp)
+    }
+
+    fn with_cursor(&self, cursor: Timestamp) -> Self {
+        let mut next = self.clone();
+        next.params.timestamp = cursor;
+        next
+    }
+}
+
+impl Paginated for GetAddressTransactions {
+    type Item = AddressTransaction;
+    type Key = TxHash;
+
+    fn items(response: Self::Response) -> Vec<AddressTransaction> {
+        response.into_iter().collect()
+    }
+
+    fn timestamp(item: &AddressTransaction) -> i64 {
+        item.timestamp.timestamp()
+    }
+
+    fn key(item: &AddressTransaction) -> TxHash {
+        item.hash
+    }
+
+    fn cursor(&self) -> Option<i64> {
+        timestamp_cursor(self.params.timestamp)
+    }
+
+    fn with_cursor(&self, cursor: Timestamp) -> Self {
+        let mut next = self.clone();
+        next.params.timestamp = cursor;
+        next
+    }
+}
+
+/// Where a paginated walk stops, besides running out of rows.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PageOptions {
+    pub stop_at: Option<Timestamp>,
+    pub max_items: Option<usize>,
+}
+
+impl PageOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        PageOptions::default()
+    }
+
+    // Rows older than this are not yielded
+    #[must_use]
+    pub fn with_stop_at(mut self, stop_at: Timestamp) -> Self {
+        self.stop_at = Some(stop_at);
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+}
+
+// Cursor state shared by the blocking iterator and the async stream. Each page is
+// requested at the oldest timestamp seen so far, so rows on that boundary come back
+// again and are dropped by key.
+struct PageCursor<P: Paginated> {
+    endpoint: P,
+    options: PageOptions,
+    cursor: Option<i64>,
+    boundary: HashSet<P::Key>,
+    buffer: VecDeque<P::Item>,
+    yielded: usize,
+    done: bool,
+}
+
+impl<P: Paginated> PageCursor<P> {
+    fn new(endpoint: P, options: PageOptions) -> Self {
+        PageCursor {
+            cursor: endpoint.cursor(),
+            endpoint,
+            options,
+            boundary: HashSet::new(),
+            buffer: VecDeque::new(),
+            yielded: 0,
+            done: false,
+        }
+    }
+
+    fn next_request(&self) -> Option<P> {
+        if self.done {
+            return None;
+        }
+        let request = match self.cursor {
+            Some(seconds) => {
+                let cursor = Utc.timestamp_opt(seconds, 0).single()?;
+                self.endpoint.with_cursor(Timestamp::from(cursor))
+            }
+            None => self.endpoint.clone(),
+        };
+        Some(request)
+    }
+
+    fn accept(&mut self, response: P::Response, limit: u64) -> Result<(), EthplorerError> {
+        let items = P::items(response);
+        let rows = items.len();
+        let Some(oldest) = items.iter().map(P::timestamp).min() else {
+            self.done = true;
+            return Ok(());
+        };
+        if limit != 0 && u64::try_from(items.len()).is_ok_and(|len| len < limit) {
+            self.done = true;
+        }
+
+        let stop_at = self.options.stop_at.map(|stop_at| stop_at.timestamp());
+        let stalled = self.cursor == Some(oldest);
+        let mut boundary = HashSet::new();
+        let mut fresh = 0;
+        for item in items {
+            let timestamp = P::timestamp(&item);
+            let key = P::key(&item);
+            if self.boundary.contains(&key) || boundary.contains(&key) {
+                continue;
+            }
+            if stop_at.is_some_and(|stop_at| timestamp < stop_at) {
+                self.done = true;
+                continue;
+            }
+            if timestamp == oldest {
+                boundary.insert(key);
+            }
+            self.buffer.push_back(item);
+            fresh += 1;
+        }
+
+        // Nothing new normally means the history ran out. Only a full page at an
+        // explicit limit says more rows share the boundary than one page can hold.
+        if fresh == 0 && !self.done {
+            let full = limit != 0 && u64::try_from(rows).is_ok_and(|rows| rows >= limit);
+            if stalled && full {
+                return Err(EthplorerError::PageStalled {
+                    timestamp: oldest,
+                    rows,
+                });
+            }
+            self.done = true;
+        }
+        if stalled {
+            self.boundary.extend(boundary);
+        } else {
+            self.cursor = Some(oldest);
+            self.boundary = boundary;
+        }
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<P::Item> {
+        if self
+            .options
+            .max_items
+            .is_some_and(|max_items| self.yielded >= max_items)
+        {
+            self.fail();
+            return None;
+        }
+        let item = self.buffer.pop_front()?;
+        self.yielded += 1;
+        Some(item)
+    }
+
+    fn fail(&mut self) {
+        self.done = true;
+        self.buffer.clear();
+    }
+}
+
+/// Blocking iterator over every row of a paginated endpoint, newest first.
+pub struct Pages<'a, T, P: Paginated> {
+    client: &'a Client<T>,
+    cursor: PageCursor<P>,
+}
+
+impl<T: Transport, P: Paginated> Iterator for Pages<'_, T, P> {
+    type Item = Result<P::Item, EthplorerError>;
+
+    // A failed page is yielded once and ends the walk
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.cursor.pop() {
+                return Some(Ok(item));
+            }
+            let request = self.cursor.next_request()?.to_config(self.client.config());
+            let accepted = self
+                .client
+                .execute(&request)
+                .and_then(|response| self.cursor.accept(response, sent_limit(&request)));
+            if let Err(err) = accepted {
+                self.cursor.fail();
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+impl<T: Transport> Client<T> {
+    #[must_use]
+    pub fn paginate<P: Paginated>(&self, endpoint: P, options: PageOptions) -> Pages<'_, T, P> {
+        Pages {
+            client: self,
+            cursor: PageCursor::new(endpoint, options),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncTransport> AsyncClient<T> {
+    // Async counterpart of `Client::paginate`
+    pub fn paginate<'a, P: Paginated + 'a>(
+        &'a self,
+        endpoint: P,
+        options: PageOptions,
+    ) -> impl futures::Stream<Item = Result<P::Item, EthplorerError>> + 'a {
+        let cursor = PageCursor::new(endpoint, options);
+        futures::stream::unfold(Some(cursor), move |state| async move {
+            let mut cursor = state?;
+            loop {
+                if let Some(item) = cursor.pop() {
+                    return Some((Ok(item), Some(cursor)));
+                }
+                let request = cursor.next_request()?.to_config(self.config());
+                let accepted = match self.execute(&request).await {
+                    Ok(response) => cursor.accept(response, sent_limit(&request)),
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = accepted {
+                    return Some((Err(err), None));
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ClientConfig, LimitPolicy};
+    use crate::rate_limit::Plan;
+    use crate::transport::{HttpResponse, MemoryTransport};
+    use crate::types::GetAddressTransactionsParams;
+
+    const ADDRESS: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+    const URL: &str =
+        "https://api.ethplorer.io/getAddressTransactions/0xdac17f958d2ee523a2206206994597c13d831ec7";
+
+    fn transaction(timestamp: i64, hash: u8) -> String {
+        format!(
+            r#"{{"timestamp":{timestamp},"from":"{ADDRESS}","to":"{ADDRESS}","hash":"0x{hash:064x}","value":0,"input":"0x","success":true}}"#
+        )
+    }
+
+    fn page(rows: &[(i64, u8)]) -> HttpResponse {
+        let rows: Vec<String> = rows
+            .iter()
+            .map(|(timestamp, hash)| transaction(*timestamp, *hash))
+            .collect();
+        HttpResponse::new(200, format!("[{}]", rows.join(",")))
+    }
+
+    fn transport() -> MemoryTransport {
+        MemoryTransport::new()
+            .with_response(URL, page(&[(300, 1), (200, 2)]))
+            .with_response(URL, page(&[(200, 2), (100, 3)]))
+            .with_response(URL, page(&[(100, 3)]))
+    }
+
+    fn endpoint() -> GetAddressTransactions {
+        GetAddressTransactions {
+            address: ADDRESS.parse().unwrap(),
+            params: GetAddressTransactionsParams {
+                limit: 2,
+                ..GetAddressTransactionsParams::default()
+            },
+        }
+    }
+
+    fn hashes(rows: &[AddressTransaction]) -> Vec<u8> {
+        rows.iter().map(|row| row.hash.as_bytes()[31]).collect()
+    }
+
+    fn cursors(transport: &MemoryTransport) -> Vec<Option<String>> {
+        transport
+            .requests()
+            .iter()
+            .map(|request| {
+                request
+                    .params
+                    .iter()
+                    .find(|(name, _)| name == "timestamp")
+                    .map(|(_, value)| value.clone())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn paginate_dedupes_boundary_rows() {
+        let client = Client::new(transport());
+        let rows: Vec<AddressTransaction> = client
+            .paginate(endpoint(), PageOptions::new())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(hashes(&rows), vec![1, 2, 3]);
+        assert_eq!(
+            cursors(client.transport()),
+            vec![None, Some("200".to_string()), Some("100".to_string())]
+        );
+    }
+
+    #[test]
+    fn paginate_stops_at_time_or_count() {
+        let client = Client::new(transport());
+        let options =
+            PageOptions::new().with_stop_at(Timestamp::from(Utc.timestamp_opt(150, 0).unwrap()));
+        let rows: Vec<AddressTransaction> = client
+            .paginate(endpoint(), options)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(hashes(&rows), vec![1, 2]);
+        assert_eq!(client.transport().requests().len(), 2);
+
+        let client = Client::new(transport());
+        let rows: Vec<AddressTransaction> = client
+            .paginate(endpoint(), PageOptions::new().with_max_items(2))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(hashes(&rows), vec![1, 2]);
+        assert_eq!(client.transport().requests().len(), 1);
+    }
+
+    #[test]
+    fn paginate_ends_on_error() {
+        let client = Client::new(
+            MemoryTransport::new()
+                .with_response(URL, page(&[(300, 1), (200, 2)]))
+                .with_response(URL, HttpResponse::new(500, "")),
+        );
+        let rows: Vec<_> = client.paginate(endpoint(), PageOptions::new()).collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[2].is_err());
+    }
+
+    #[test]
+    fn paginate_follows_clamped_limit() {
+        let full: Vec<(i64, u8)> = (0..100_u8)
+            .map(|row| (1000 - i64::from(row), row))
+            .collect();
+        let client = Client::new(
+            MemoryTransport::new()
+                .with_response(URL, page(&full))
+                .with_response(URL, page(&[(901, 99), (900, 100)])),
+        )
+        .with_config(
+            ClientConfig::new()
+                .with_plan(Plan::Freekey)
+                .with_limit_policy(LimitPolicy::Clamp),
+        );
+        let mut endpoint = endpoint();
+        endpoint.params.limit = 500;
+        let rows: Vec<AddressTransaction> = client
+            .paginate(endpoint, PageOptions::new())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 101);
+        assert_eq!(client.transport().requests().len(), 2);
+    }
+
+    #[test]
+    fn paginate_reports_full_page_on_one_timestamp() {
+        let client = Client::new(
+            MemoryTransport::new()
+                .with_response(URL, page(&[(300, 1), (300, 2)]))
+                .with_response(URL, page(&[(300, 1), (300, 2)])),
+        );
+        let rows: Vec<_> = client.paginate(endpoint(), PageOptions::new()).collect();
+        assert_eq!(rows.len(), 3);
+        match &rows[2] {
+            Err(EthplorerError::PageStalled { timestamp, rows }) => {
+                assert_eq!((*timestamp, *rows), (300, 2));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn paginate_ends_on_boundary_rows_with_default_limit() {
+        let transport = MemoryTransport::new()
+            .with_response(URL, page(&[(300, 1), (200, 2)]))
+            .with_response(URL, page(&[(200, 2), (100, 3)]))
+            .with_response(URL, page(&[(100, 3)]));
+        let client = Client::new(transport);
+        let mut endpoint = endpoint();
+        endpoint.params.limit = 0;
+        let rows: Vec<AddressTransaction> = client
+            .paginate(endpoint, PageOptions::new())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(hashes(&rows), vec![1, 2, 3]);
+        assert_eq!(client.transport().requests().len(), 3);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_paginate_works() {
+        use futures::StreamExt;
+
+        let client = AsyncClient::new(transport());
+        let rows: Vec<_> = futures::executor::block_on(
+            client
+                .paginate(endpoint(), PageOptions::new())
+                .collect::<Vec<_>>(),
+        );
+        let rows: Vec<AddressTransaction> = rows.into_iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(hashes(&rows), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,935 @@
+use crate::address::{Address, HexError, TxHash};
+use crate::config::ClientConfig;
+use crate::consts::{
+    ADD_POOL_ADDRESSES_ROUTE, CLEAR_POOL_ADDRESSES_ROUTE, CREATE_POOL_ROUTE,
+    DELETE_POOL_ADDRESSES_ROUTE, DELETE_POOL_ROUTE, GET_ADDRESS_HISTORY, GET_ADDRESS_INFO_ROUTE,
+    GET_ADDRESS_TRANSACTIONS_ROUTE, GET_LAST_BLOCK_ROUTE, GET_POOL_ADDRESSES_ROUTE,
+    GET_POOL_LAST_OPERATIONS_ROUTE, GET_POOL_LAST_TRANSACTIONS_ROUTE, GET_POOL_UPDATES_ROUTE,
+    GET_TOKENS_NEW_ROUTE, GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE, GET_TOKEN_HISTORY_ROUTE,
+    GET_TOKEN_INFO_ROUTE, GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE, GET_TOP_ROUTE,
+    GET_TOP_TOKENS_ROUTE, GET_TOP_TOKEN_HOLDERS_ROUTE, GET_TX_INFO_ROUTE,
+};
+use crate::endpoint::{
+    AddPoolAddresses, ClearPoolAddresses, CreatePool, DeletePool, DeletePoolAddresses, Endpoint,
+    GetAddressHistory, GetAddressInfo, GetAddressTransactions, GetLastBlock, GetPoolAddresses,
+    GetPoolLastOperations, GetPoolLastTransactions, GetPoolUpdates, GetTokenDailyPriceHistory,
+    GetTokenDailyTransactionCount, GetTokenHistory, GetTokenInfo, GetTokensNew, GetTop,
+    GetTopTokenHolders, GetTopTokens, GetTxInfo,
+};
+use crate::types::{
+    CreatePoolParams, GetAddressHistoryParams, GetAddressInfoParams, GetAddressTransactionsParams,
+    GetTokenHistoryParams, GetTopParams, OperationType, PoolAddressesParams, RequestConfig,
+    Timestamp, TopCriteria,
+};
+use chrono::{TimeZone, Utc};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UrlParseError {
+    InvalidUrl(String),
+    InvalidEncoding(String),
+    UnknownRoute(String),
+    MissingAddress {
+        route: &'static str,
+    },
+    InvalidAddress {
+        value: String,
+        reason: HexError,
+    },
+    // A transaction hash or pool id segment, for the routes that take one
+    MissingSegment {
+        route: &'static str,
+        expected: &'static str,
+    },
+    InvalidHash {
+        value: String,
+        reason: HexError,
+    },
+    UnexpectedSegment {
+        route: &'static str,
+        segment: String,
+    },
+    UnknownParam {
+        route: &'static str,
+        name: String,
+    },
+    DuplicateParam(String),
+    InvalidParam {
+        name: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl Display for UrlParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            UrlParseError::InvalidUrl(url) => write!(f, "invalid url: {url}"),
+            UrlParseError::InvalidEncoding(value) => {
+                write!(f, "invalid percent-encoding in {value:?}")
+            }
+            UrlParseError::UnknownRoute(route) => write!(f, "unknown route {route:?}"),
+            UrlParseError::MissingAddress { route } => {
+                write!(f, "route {route} requires an address segment")
+            }
+            UrlParseError::InvalidAddress { value, reason } => {
+                write!(f, "invalid address {value:?}: {reason}")
+            }
+            UrlParseError::MissingSegment { route, expected } => {
+                write!(f, "route {route} requires a {expected} segment")
+            }
+            UrlParseError::InvalidHash { value, reason } => {
+                write!(f, "invalid transaction hash {value:?}: {reason}")
+            }
+            UrlParseError::UnexpectedSegment { route, segment } => {
+                write!(f, "unexpected segment {segment:?} after route {route}")
+            }
+            UrlParseError::UnknownParam { route, name } => {
+                write!(f, "unknown parameter {name:?} for route {route}")
+            }
+            UrlParseError::DuplicateParam(name) => write!(f, "duplicate parameter {name:?}"),
+            UrlParseError::InvalidParam {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid value {value:?} for {name}: {reason}"),
+        }
+    }
+}
+
+impl Error for UrlParseError {}
+
+// The typed request behind one of the routes in `consts.rs`
+#[derive(Debug)]
+pub enum ParsedRequest {
+    GetAddressInfo(GetAddressInfo),
+    GetTokenInfo(GetTokenInfo),
+    GetTopTokenHolders(GetTopTokenHolders),
+    GetLastBlock(GetLastBlock),
+    GetTokensNew(GetTokensNew),
+    GetTokenDailyTransactionCount(GetTokenDailyTransactionCount),
+    GetTokenHistory(GetTokenHistory),
+    GetAddressHistory(GetAddressHistory),
+    GetAddressTransactions(GetAddressTransactions),
+    GetTopTokens(GetTopTokens),
+    GetTop(GetTop),
+    GetTokenDailyPriceHistory(GetTokenDailyPriceHistory),
+    GetTxInfo(GetTxInfo),
+    CreatePool(CreatePool),
+    DeletePool(DeletePool),
+    AddPoolAddresses(AddPoolAddresses),
+    DeletePoolAddresses(DeletePoolAddresses),
+    ClearPoolAddresses(ClearPoolAddresses),
+    GetPoolAddresses(GetPoolAddresses),
+    GetPoolLastOperations(GetPoolLastOperations),
+    GetPoolLastTransactions(GetPoolLastTransactions),
+    GetPoolUpdates(GetPoolUpdates),
+}
+
+impl ParsedRequest {
+    #[must_use]
+    pub fn route(&self) -> &'static str {
+        match self {
+            ParsedRequest::GetAddressInfo(_) => GET_ADDRESS_INFO_ROUTE,
+            ParsedRequest::GetTokenInfo(_) => GET_TOKEN_INFO_ROUTE,
+            ParsedRequest::GetTopTokenHolders(_) => GET_TOP_TOKEN_HOLDERS_ROUTE,
+            ParsedRequest::GetLastBlock(_) => GET_LAST_BLOCK_ROUTE,
+            ParsedRequest::GetTokensNew(_) => GET_TOKENS_NEW_ROUTE,
+            ParsedRequest::GetTokenDailyTransactionCount(_) => {
+                GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE
+            }
+            ParsedRequest::GetTokenHistory(_) => GET_TOKEN_HISTORY_ROUTE,
+            ParsedRequest::GetAddressHistory(_) => GET_ADDRESS_HISTORY,
+            ParsedRequest::GetAddressTransactions(_) => GET_ADDRESS_TRANSACTIONS_ROUTE,
+            ParsedRequest::GetTopTokens(_) => GET_TOP_TOKENS_ROUTE,
+            ParsedRequest::GetTop(_) => GET_TOP_ROUTE,
+            ParsedRequest::GetTokenDailyPriceHistory(_) => GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE,
+            ParsedRequest::GetTxInfo(_) => GET_TX_INFO_ROUTE,
+            ParsedRequest::CreatePool(_) => CREATE_POOL_ROUTE,
+            ParsedRequest::DeletePool(_) => DELETE_POOL_ROUTE,
+            ParsedRequest::AddPoolAddresses(_) => ADD_POOL_ADDRESSES_ROUTE,
+            ParsedRequest::DeletePoolAddresses(_) => DELETE_POOL_ADDRESSES_ROUTE,
+            ParsedRequest::ClearPoolAddresses(_) => CLEAR_POOL_ADDRESSES_ROUTE,
+            ParsedRequest::GetPoolAddresses(_) => GET_POOL_ADDRESSES_ROUTE,
+            ParsedRequest::GetPoolLastOperations(_) => GET_POOL_LAST_OPERATIONS_ROUTE,
+            ParsedRequest::GetPoolLastTransactions(_) => GET_POOL_LAST_TRANSACTIONS_ROUTE,
+            ParsedRequest::GetPoolUpdates(_) => GET_POOL_UPDATES_ROUTE,
+        }
+    }
+
+    #[must_use]
+    pub fn address(&self) -> Option<&Address> {
+        match self {
+            ParsedRequest::GetAddressInfo(req) => Some(&req.address),
+            ParsedRequest::GetTokenInfo(req) => Some(&req.address),
+            ParsedRequest::GetTopTokenHolders(req) => Some(&req.address),
+            ParsedRequest::GetTokenDailyTransactionCount(req) => Some(&req.address),
+            ParsedRequest::GetTokenHistory(req) => Some(&req.address),
+            ParsedRequest::GetAddressHistory(req) => Some(&req.address),
+            ParsedRequest::GetAddressTransactions(req) => Some(&req.address),
+            ParsedRequest::GetTokenDailyPriceHistory(req) => Some(&req.address),
+            ParsedRequest::GetLastBlock(_)
+            | ParsedRequest::GetTokensNew(_)
+            | ParsedRequest::GetTopTokens(_)
+            | ParsedRequest::GetTop(_)
+            | ParsedRequest::GetTxInfo(_)
+            | ParsedRequest::CreatePool(_)
+            | ParsedRequest::DeletePool(_)
+            | ParsedRequest::AddPoolAddresses(_)
+            | ParsedRequest::DeletePoolAddresses(_)
+            | ParsedRequest::ClearPoolAddresses(_)
+            | ParsedRequest::GetPoolAddresses(_)
+            | ParsedRequest::GetPoolLastOperations(_)
+            | ParsedRequest::GetPoolLastTransactions(_)
+            | ParsedRequest::GetPoolUpdates(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub fn to_config(&self, config: &ClientConfig) -> RequestConfig {
+        match self {
+            ParsedRequest::GetAddressInfo(req) => req.to_config(config),
+            ParsedRequest::GetTokenInfo(req) => req.to_config(config),
+            ParsedRequest::GetTopTokenHolders(req) => req.to_config(config),
+            ParsedRequest::GetLastBlock(req) => req.to_config(config),
+            ParsedRequest::GetTokensNew(req) => req.to_config(config),
+            ParsedRequest::GetTokenDailyTransactionCount(req) => req.to_config(config),
+            ParsedRequest::GetTokenHistory(req) => req.to_config(config),
+            ParsedRequest::GetAddressHistory(req) => req.to_config(config),
+            ParsedRequest::GetAddressTransactions(req) => req.to_config(config),
+            ParsedRequest::GetTopTokens(req) => req.to_config(config),
+            ParsedRequest::GetTop(req) => req.to_config(config),
+            ParsedRequest::GetTokenDailyPriceHistory(req) => req.to_config(config),
+            ParsedRequest::GetTxInfo(req) => req.to_config(config),
+            ParsedRequest::CreatePool(req) => req.to_config(config),
+            ParsedRequest::DeletePool(req) => req.to_config(config),
+            ParsedRequest::AddPoolAddresses(req) => req.to_config(config),
+            ParsedRequest::DeletePoolAddresses(req) => req.to_config(config),
+            ParsedRequest::ClearPoolAddresses(req) => req.to_config(config),
+            ParsedRequest::GetPoolAddresses(req) => req.to_config(config),
+            ParsedRequest::GetPoolLastOperations(req) => req.to_config(config),
+            ParsedRequest::GetPoolLastTransactions(req) => req.to_config(config),
+            ParsedRequest::GetPoolUpdates(req) => req.to_config(config),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedUrl {
+    pub network: String,
+    pub api_key: String,
+    pub request: ParsedRequest,
+}
+
+impl ParsedUrl {
+    #[must_use]
+    pub fn to_config(&self) -> RequestConfig {
+        // The URL only names one host, which for the pool routes is the monitor host
+        let config = ClientConfig::new()
+            .with_base_url(&self.network)
+            .with_monitor_base_url(&self.network)
+            .with_api_key(&self.api_key);
+        self.request.to_config(&config)
+    }
+}
+
+impl FromStr for ParsedUrl {
+    type Err = UrlParseError;
+
+    fn from_str(url: &str) -> Result<Self, Self::Err> {
+        parse_request_url(url)
+    }
+}
+
+fn percent_decode(input: &str, plus_as_space: bool) -> Result<String, UrlParseError> {
+    let invalid = || UrlParseError::InvalidEncoding(input.to_string());
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                // `from_str_radix` alone would also take a sign, as in `%+5`
+                let hex = input
+                    .get(i + 1..i + 3)
+                    .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                    .ok_or_else(invalid)?;
+                out.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+                i += 3;
+            }
+            b'+' if plus_as_space => {
+                out.push(b' ');
+                i += 1;
+            }
+            byte => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(out).map_err(|_| invalid())
+}
+
+// Parameters pulled off the query string; each lookup removes the entry so that
+// whatever is left over at the end is unknown to the route
+struct QueryParams {
+    route: &'static str,
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryParams {
+    fn parse(route: &'static str, query: &str) -> Result<Self, UrlParseError> {
+        let mut pairs: Vec<(String, String)> = vec![];
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = match pair.find('=') {
+                Some(idx) => (&pair[..idx], &pair[idx + 1..]),
+                None => (pair, ""),
+            };
+            let name = percent_decode(name, true)?;
+            let value = percent_decode(value, true)?;
+            if pairs.iter().any(|(existing, _)| *existing == name) {
+                return Err(UrlParseError::DuplicateParam(name));
+            }
+            pairs.push((name, value));
+        }
+        Ok(QueryParams { route, pairs })
+    }
+
+    fn take(&mut self, name: &str) -> Option<String> {
+        let idx = self.pairs.iter().position(|(key, _)| key == name)?;
+        Some(self.pairs.remove(idx).1)
+    }
+
+    // Whatever the route doesn't know, for routes that pass it on as `extra_params`
+    fn take_rest(&mut self) -> Vec<(String, String)> {
+        std::mem::take(&mut self.pairs)
+    }
+
+    fn take_string(&mut self, name: &str) -> String {
+        self.take(name).unwrap_or_default()
+    }
+
+    fn take_address(&mut self, name: &str) -> Result<Option<Address>, UrlParseError> {
+        match self.take(name) {
+            Some(value) => match value.parse() {
+                Ok(address) => Ok(Some(address)),
+                Err(err) => Err(UrlParseError::InvalidParam {
+                    name: name.to_string(),
+                    value,
+                    reason: format!("{err}"),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn take_addresses(&mut self, name: &str) -> Result<Vec<Address>, UrlParseError> {
+        let Some(value) = self.take(name) else {
+            return Ok(vec![]);
+        };
+        value
+            .split(',')
+            .filter(|address| !address.is_empty())
+            .map(|address| {
+                address.parse().map_err(|err| UrlParseError::InvalidParam {
+                    name: name.to_string(),
+                    value: value.clone(),
+                    reason: format!("{err}"),
+                })
+            })
+            .collect()
+    }
+
+    fn take_u64(&mut self, name: &str) -> Result<u64, UrlParseError> {
+        match self.take(name) {
+            Some(value) => value.parse().map_err(|err| UrlParseError::InvalidParam {
+                name: name.to_string(),
+                value,
+                reason: format!("{err}"),
+            }),
+            None => Ok(0),
+        }
+    }
+
+    fn take_bool(&mut self, name: &str) -> Result<bool, UrlParseError> {
+        match self.take(name).as_deref() {
+            None | Some("false" | "0") => Ok(false),
+            Some("true" | "1") => Ok(true),
+            Some(value) => Err(UrlParseError::InvalidParam {
+                name: name.to_string(),
+                value: value.to_string(),
+                reason: "expected true, false, 1 or 0".to_string(),
+            }),
+        }
+    }
+
+    fn take_timestamp(&mut self, name: &str) -> Result<Timestamp, UrlParseError> {
+        self.take(name).map_or(Ok(Timestamp::default()), |value| {
+            parse_timestamp(name, value)
+        })
+    }
+
+    fn finish(self) -> Result<(), UrlParseError> {
+        match self.pairs.into_iter().next() {
+            Some((name, _)) => Err(UrlParseError::UnknownParam {
+                route: self.route,
+                name,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_timestamp(name: &str, value: String) -> Result<Timestamp, UrlParseError> {
+    let seconds = value.parse::<i64>().map_err(|err| format!("{err}"));
+    let date = seconds.and_then(|seconds| {
+        Utc.timestamp_opt(seconds, 0)
+            .single()
+            .ok_or_else(|| "timestamp out of range".to_string())
+    });
+    match date {
+        Ok(date) => Ok(Timestamp::from(date)),
+        Err(reason) => Err(UrlParseError::InvalidParam {
+            name: name.to_string(),
+            value,
+            reason,
+        }),
+    }
+}
+
+fn route_constant(segment: &str) -> Result<&'static str, UrlParseError> {
+    let route = match segment {
+        GET_ADDRESS_INFO_ROUTE => GET_ADDRESS_INFO_ROUTE,
+        GET_TOKEN_INFO_ROUTE => GET_TOKEN_INFO_ROUTE,
+        GET_TOP_TOKEN_HOLDERS_ROUTE => GET_TOP_TOKEN_HOLDERS_ROUTE,
+        GET_LAST_BLOCK_ROUTE => GET_LAST_BLOCK_ROUTE,
+        GET_TOKENS_NEW_ROUTE => GET_TOKENS_NEW_ROUTE,
+        GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE => GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE,
+        GET_TOKEN_HISTORY_ROUTE => GET_TOKEN_HISTORY_ROUTE,
+        GET_ADDRESS_HISTORY => GET_ADDRESS_HISTORY,
+        GET_ADDRESS_TRANSACTIONS_ROUTE => GET_ADDRESS_TRANSACTIONS_ROUTE,
+        GET_TOP_TOKENS_ROUTE => GET_TOP_TOKENS_ROUTE,
+        GET_TOP_ROUTE => GET_TOP_ROUTE,
+        GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE => GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE,
+        GET_TX_INFO_ROUTE => GET_TX_INFO_ROUTE,
+        CREATE_POOL_ROUTE => CREATE_POOL_ROUTE,
+        DELETE_POOL_ROUTE => DELETE_POOL_ROUTE,
+        ADD_POOL_ADDRESSES_ROUTE => ADD_POOL_ADDRESSES_ROUTE,
+        DELETE_POOL_ADDRESSES_ROUTE => DELETE_POOL_ADDRESSES_ROUTE,
+        CLEAR_POOL_ADDRESSES_ROUTE => CLEAR_POOL_ADDRESSES_ROUTE,
+        GET_POOL_ADDRESSES_ROUTE => GET_POOL_ADDRESSES_ROUTE,
+        GET_POOL_LAST_OPERATIONS_ROUTE => GET_POOL_LAST_OPERATIONS_ROUTE,
+        GET_POOL_LAST_TRANSACTIONS_ROUTE => GET_POOL_LAST_TRANSACTIONS_ROUTE,
+        GET_POOL_UPDATES_ROUTE => GET_POOL_UPDATES_ROUTE,
+        other => return Err(UrlParseError::UnknownRoute(other.to_string())),
+    };
+    Ok(route)
+}
+
+// What the segment after the route names, if the route takes one. The POST pool
+// routes carry everything in the form body instead.
+fn route_segment(route: &str) -> Option<&'static str> {
+    match route {
+        GET_LAST_BLOCK_ROUTE
+        | GET_TOKENS_NEW_ROUTE
+        | GET_TOP_TOKENS_ROUTE
+        | GET_TOP_ROUTE
+        | CREATE_POOL_ROUTE
+        | DELETE_POOL_ROUTE
+        | ADD_POOL_ADDRESSES_ROUTE
+        | DELETE_POOL_ADDRESSES_ROUTE
+        | CLEAR_POOL_ADDRESSES_ROUTE => None,
+        GET_TX_INFO_ROUTE => Some("transaction hash"),
+        GET_POOL_ADDRESSES_ROUTE
+        | GET_POOL_LAST_OPERATIONS_ROUTE
+        | GET_POOL_LAST_TRANSACTIONS_ROUTE
+        | GET_POOL_UPDATES_ROUTE => Some("pool id"),
+        _ => Some("address"),
+    }
+}
+
+fn build_request(
+    route: &'static str,
+    segment: String,
+    query: &mut QueryParams,
+) -> Result<ParsedRequest, UrlParseError> {
+    let parse_address = || {
+        segment
+            .parse::<Address>()
+            .map_err(|reason| UrlParseError::InvalidAddress {
+                value: segment.clone(),
+                reason,
+            })
+    };
+    let request = match route {
+        GET_ADDRESS_INFO_ROUTE => ParsedRequest::GetAddressInfo(GetAddressInfo {
+            address: parse_address()?,
+            params: GetAddressInfoParams {
+                token: query.take_address("token")?,
+                show_eth_totals: query.take_bool("showETHTotals")?,
+                show_txs_count: query.take_bool("showTxsCount")?,
+                extra_params: query.take_rest(),
+            },
+        }),
+        GET_TOKEN_INFO_ROUTE => ParsedRequest::GetTokenInfo(GetTokenInfo {
+            address: parse_address()?,
+        }),
+        GET_TOP_TOKEN_HOLDERS_ROUTE => ParsedRequest::GetTopTokenHolders(GetTopTokenHolders {
+            address: parse_address()?,
+            limit: query.take_u64("limit")?,
+        }),
+        GET_LAST_BLOCK_ROUTE => ParsedRequest::GetLastBlock(GetLastBlock),
+        GET_TOKENS_NEW_ROUTE => ParsedRequest::GetTokensNew(GetTokensNew),
+        GET_TOKEN_DAILY_TRANSACTION_COUNT_ROUTE => {
+            ParsedRequest::GetTokenDailyTransactionCount(GetTokenDailyTransactionCount {
+                address: parse_address()?,
+                period: query.take_u64("period")?,
+            })
+        }
+        GET_TOKEN_HISTORY_ROUTE => ParsedRequest::GetTokenHistory(GetTokenHistory {
+            address: parse_address()?,
+            params: GetTokenHistoryParams {
+                history_type: query
+                    .take("type")
+                    .map(|value| OperationType::from(value.as_str())),
+                limit: query.take_u64("limit")?,
+                timestamp: query.take_timestamp("timestamp")?,
+                extra_params: query.take_rest(),
+            },
+        }),
+        GET_ADDRESS_HISTORY => ParsedRequest::GetAddressHistory(GetAddressHistory {
+            address: parse_address()?,
+            params: GetAddressHistoryParams {
+                history_type: query
+                    .take("type")
+                    .map(|value| OperationType::from(value.as_str())),
+                limit: query.take_u64("limit")?,
+                timestamp: query.take_timestamp("timestamp")?,
+                token: query.take_address("token")?,
+                extra_params: query.take_rest(),
+            },
+        }),
+        GET_ADDRESS_TRANSACTIONS_ROUTE => {
+            ParsedRequest::GetAddressTransactions(GetAddressTransactions {
+                address: parse_address()?,
+                params: GetAddressTransactionsParams {
+                    limit: query.take_u64("limit")?,
+                    timestamp: query.take_timestamp("timestamp")?,
+                    show_zero_values: query.take_bool("showZeroValues")?,
+                    extra_params: query.take_rest(),
+                },
+            })
+        }
+        GET_TOP_TOKENS_ROUTE => ParsedRequest::GetTopTokens(GetTopTokens),
+        GET_TOP_ROUTE => ParsedRequest::GetTop(GetTop {
+            params: GetTopParams {
+                limit: query.take_u64("limit")?,
+                criteria: query
+                    .take("criteria")
+                    .map(|value| TopCriteria::from(value.as_str())),
+                extra_params: query.take_rest(),
+            },
+        }),
+        GET_TOKEN_PRICE_HISTORY_GROUPED_ROUTE => {
+            ParsedRequest::GetTokenDailyPriceHistory(GetTokenDailyPriceHistory {
+                address: parse_address()?,
+                period: query.take_u64("period")?,
+            })
+        }
+        GET_TX_INFO_ROUTE => ParsedRequest::GetTxInfo(GetTxInfo {
+            hash: segment
+                .parse::<TxHash>()
+                .map_err(|reason| UrlParseError::InvalidHash {
+                    value: segment.clone(),
+                    reason,
+                })?,
+        }),
+        other => return build_pool_request(other, segment, query),
+    };
+    Ok(request)
+}
+
+fn build_pool_request(
+    route: &str,
+    segment: String,
+    query: &mut QueryParams,
+) -> Result<ParsedRequest, UrlParseError> {
+    let request = match route {
+        CREATE_POOL_ROUTE => ParsedRequest::CreatePool(CreatePool {
+            params: CreatePoolParams {
+                addresses: query.take_addresses("addresses")?,
+            },
+        }),
+        DELETE_POOL_ROUTE => ParsedRequest::DeletePool(DeletePool {
+            pool_id: query.take_string("poolId"),
+        }),
+        ADD_POOL_ADDRESSES_ROUTE => ParsedRequest::AddPoolAddresses(AddPoolAddresses {
+            params: PoolAddressesParams {
+                pool_id: query.take_string("poolId"),
+                addresses: query.take_addresses("addresses")?,
+            },
+        }),
+        DELETE_POOL_ADDRESSES_ROUTE => ParsedRequest::DeletePoolAddresses(DeletePoolAddresses {
+            params: PoolAddressesParams {
+                pool_id: query.take_string("poolId"),
+                addresses: query.take_addresses("addresses")?,
+            },
+        }),
+        CLEAR_POOL_ADDRESSES_ROUTE => ParsedRequest::ClearPoolAddresses(ClearPoolAddresses {
+            pool_id: query.take_string("poolId"),
+        }),
+        GET_POOL_ADDRESSES_ROUTE => {
+            ParsedRequest::GetPoolAddresses(GetPoolAddresses { pool_id: segment })
+        }
+        GET_POOL_LAST_OPERATIONS_ROUTE => {
+            ParsedRequest::GetPoolLastOperations(GetPoolLastOperations {
+                pool_id: segment,
+                period: query.take_u64("period")?,
+            })
+        }
+        GET_POOL_LAST_TRANSACTIONS_ROUTE => {
+            ParsedRequest::GetPoolLastTransactions(GetPoolLastTransactions {
+                pool_id: segment,
+                period: query.take_u64("period")?,
+            })
+        }
+        GET_POOL_UPDATES_ROUTE => ParsedRequest::GetPoolUpdates(GetPoolUpdates {
+            pool_id: segment,
+            period: query.take_u64("period")?,
+        }),
+        other => return Err(UrlParseError::UnknownRoute(other.to_string())),
+    };
+    Ok(request)
+}
+
+/// Reverse of the `get_*_config` builders: recovers the typed request from a URL.
+///
+/// The POST pool routes send their parameters as a form body, which is read here from
+/// the query string, so append `form_body()` to `url()` to parse one of those.
+///
+/// # Errors
+/// Returns a `UrlParseError` naming the route, segment or parameter that could not be
+/// understood.
+pub fn parse_request_url(url: &str) -> Result<ParsedUrl, UrlParseError> {
+    let invalid_url = || UrlParseError::InvalidUrl(url.to_string());
+
+    let scheme_end = url.find("://").ok_or_else(invalid_url)?;
+    let rest = &url[scheme_end + 3..];
+    let (location, query) = match rest.find('?') {
+        Some(idx) => (&rest[..idx], &rest[idx + 1..]),
+        None => (rest, ""),
+    };
+    let (host, path) = match location.find('/') {
+        Some(idx) => (&location[..idx], &location[idx + 1..]),
+        None => (location, ""),
+    };
+    if host.is_empty() {
+        return Err(invalid_url());
+    }
+
+    let raw_segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let decoded = raw_segments
+        .iter()
+        .map(|segment| percent_decode(segment, false))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Segments ahead of the route belong to the base URL, e.g. a proxy mounted under
+    // a path; with no known route at all, the first segment is reported
+    let route_at = decoded
+        .iter()
+        .position(|segment| route_constant(segment).is_ok());
+    let Some(route_at) = route_at else {
+        let first = decoded.into_iter().next().unwrap_or_default();
+        return Err(UrlParseError::UnknownRoute(first));
+    };
+    let route = route_constant(&decoded[route_at])?;
+    let mut network = format!("{}{}", &url[..scheme_end + 3], host);
+    for segment in &raw_segments[..route_at] {
+        network.push('/');
+        network.push_str(segment);
+    }
+
+    let mut segments = decoded.into_iter().skip(route_at + 1);
+    let segment = match route_segment(route) {
+        Some(expected) => segments.next().ok_or(if expected == "address" {
+            UrlParseError::MissingAddress { route }
+        } else {
+            UrlParseError::MissingSegment { route, expected }
+        })?,
+        None => String::new(),
+    };
+    if let Some(segment) = segments.next() {
+        return Err(UrlParseError::UnexpectedSegment { route, segment });
+    }
+
+    let mut query = QueryParams::parse(route, query)?;
+    let api_key = query.take_string("apiKey");
+    let request = build_request(route, segment, &mut query)?;
+    query.finish()?;
+
+    Ok(ParsedUrl {
+        network,
+        api_key,
+        request,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::MONITOR_NETWORK;
+    use crate::{
+        add_pool_addresses_config, get_address_history_config, get_address_info_config,
+        get_pool_last_operations_config, get_top_config, get_tx_info_config, NETWORK,
+    };
+
+    const ADDRESS: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+
+    #[test]
+    fn parse_request_url_works() {
+        let parsed = parse_request_url(
+            &format!(
+                "https://api.ethplorer.io/getAddressHistory/{ADDRESS}?apiKey=key&limit=50&type=transfer&timestamp=1600000000"
+            ),
+        )
+        .unwrap();
+        assert_eq!(parsed.network, NETWORK);
+        assert_eq!(parsed.api_key, "key");
+        assert_eq!(parsed.request.route(), GET_ADDRESS_HISTORY);
+        assert_eq!(parsed.request.address(), Some(&ADDRESS.parse().unwrap()));
+        match &parsed.request {
+            ParsedRequest::GetAddressHistory(req) => {
+                assert_eq!(req.params.limit, 50);
+                assert_eq!(req.params.history_type, Some(OperationType::Transfer));
+                assert_eq!(req.params.timestamp.timestamp(), 1_600_000_000);
+                assert_eq!(req.params.token, None);
+            }
+            other => panic!("unexpected request {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_request_url_round_trips() {
+        let config = get_address_history_config(
+            &ClientConfig::new().with_api_key("key"),
+            &ADDRESS.parse().unwrap(),
+            &GetAddressHistoryParams {
+                history_type: Some(OperationType::Unknown("tranfer".to_string())),
+                limit: 20,
+                timestamp: Timestamp::default(),
+                token: Some(ADDRESS.parse().unwrap()),
+                extra_params: vec![],
+            },
+        );
+        let parsed: ParsedUrl = config.url().parse().unwrap();
+        assert_eq!(parsed.to_config(), config);
+
+        let config = get_top_config(
+            &ClientConfig::default(),
+            &GetTopParams {
+                limit: 10,
+                criteria: Some(TopCriteria::Count),
+                extra_params: vec![],
+            },
+        );
+        let parsed: ParsedUrl = config.url().parse().unwrap();
+        assert_eq!(parsed.request.address(), None);
+        assert_eq!(parsed.to_config(), config);
+    }
+
+    #[test]
+    fn parse_request_url_keeps_extra_params() {
+        let config = get_address_info_config(
+            &ClientConfig::new().with_api_key("key"),
+            &ADDRESS.parse().unwrap(),
+            &GetAddressInfoParams {
+                token: None,
+                show_eth_totals: true,
+                show_txs_count: false,
+                extra_params: vec![
+                    ("showTokens".to_string(), "false".to_string()),
+                    ("note".to_string(), "a b&c".to_string()),
+                ],
+            },
+        );
+        let parsed: ParsedUrl = config.url().parse().unwrap();
+        match &parsed.request {
+            ParsedRequest::GetAddressInfo(req) => {
+                assert_eq!(req.params.extra_params[1].1, "a b&c");
+            }
+            other => panic!("unexpected request {:?}", other),
+        }
+        assert_eq!(parsed.to_config(), config);
+
+        let config = get_top_config(
+            &ClientConfig::default(),
+            &GetTopParams {
+                limit: 10,
+                criteria: Some(TopCriteria::Cap),
+                extra_params: vec![("period".to_string(), "7".to_string())],
+            },
+        );
+        let parsed: ParsedUrl = config.url().parse().unwrap();
+        assert_eq!(parsed.to_config(), config);
+    }
+
+    #[test]
+    fn parse_request_url_knows_every_route() {
+        // Scans consts.rs itself so that a route added there without a parser fails here
+        let routes: Vec<&str> = include_str!("consts.rs")
+            .lines()
+            .filter(|line| line.starts_with("pub const ") && line.contains(": &str = \""))
+            .filter_map(|line| line.split('"').nth(1))
+            .filter(|value| !value.contains("://"))
+            .collect();
+        assert_eq!(routes.len(), 22);
+        for route in routes {
+            assert_eq!(route_constant(route), Ok(route));
+        }
+    }
+
+    #[test]
+    fn parse_request_url_round_trips_tx_info_and_pools() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        let config = get_tx_info_config(
+            &ClientConfig::new().with_api_key("key"),
+            &hash.parse().unwrap(),
+        );
+        let parsed: ParsedUrl = config.url().parse().unwrap();
+        assert_eq!(parsed.request.route(), GET_TX_INFO_ROUTE);
+        assert_eq!(parsed.to_config(), config);
+
+        let config = get_pool_last_operations_config(&ClientConfig::default(), "pool-1", 3600);
+        let parsed: ParsedUrl = config.url().parse().unwrap();
+        assert_eq!(parsed.network, MONITOR_NETWORK);
+        assert_eq!(parsed.to_config(), config);
+
+        let config = add_pool_addresses_config(
+            &ClientConfig::new().with_api_key("key"),
+            &PoolAddressesParams {
+                pool_id: "pool-1".to_string(),
+                addresses: vec![ADDRESS.parse().unwrap(), ADDRESS.parse().unwrap()],
+            },
+        );
+        let url = format!("{}?{}", config.url(), config.form_body().unwrap());
+        let parsed: ParsedUrl = url.parse().unwrap();
+        match &parsed.request {
+            ParsedRequest::AddPoolAddresses(req) => {
+                assert_eq!(req.params.pool_id, "pool-1");
+                assert_eq!(req.params.addresses.len(), 2);
+            }
+            other => panic!("unexpected request {:?}", other),
+        }
+        assert_eq!(parsed.to_config(), config);
+    }
+
+    #[test]
+    fn parse_request_url_keeps_base_path() {
+        let client = ClientConfig::new()
+            .with_base_url("http://proxy:8080/ethplorer/v1")
+            .with_api_key("key");
+        let config = get_top_config(
+            &client,
+            &GetTopParams {
+                limit: 5,
+                criteria: None,
+                extra_params: vec![],
+            },
+        );
+        let parsed: ParsedUrl = config.url().parse().unwrap();
+        assert_eq!(parsed.network, "http://proxy:8080/ethplorer/v1");
+        assert_eq!(parsed.request.route(), GET_TOP_ROUTE);
+        assert_eq!(parsed.to_config(), config);
+
+        let url = format!("http://proxy/prefix/getTokenInfo/{ADDRESS}?apiKey=key");
+        let parsed = parse_request_url(&url).unwrap();
+        assert_eq!(parsed.network, "http://proxy/prefix");
+        assert_eq!(parsed.request.address(), Some(&ADDRESS.parse().unwrap()));
+        assert_eq!(
+            parse_request_url("http://proxy/prefix/getNothing").unwrap_err(),
+            UrlParseError::UnknownRoute("prefix".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_request_url_errors() {
+        assert_eq!(
+            parse_request_url("api.ethplorer.io/getLastBlock").unwrap_err(),
+            UrlParseError::InvalidUrl("api.ethplorer.io/getLastBlock".to_string())
+        );
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getNothing/0x0").unwrap_err(),
+            UrlParseError::UnknownRoute("getNothing".to_string())
+        );
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getTokenInfo").unwrap_err(),
+            UrlParseError::MissingAddress {
+                route: GET_TOKEN_INFO_ROUTE
+            }
+        );
+        assert_eq!(
+            parse_request_url("https://api-mon.ethplorer.io/getPoolUpdates").unwrap_err(),
+            UrlParseError::MissingSegment {
+                route: GET_POOL_UPDATES_ROUTE,
+                expected: "pool id"
+            }
+        );
+        assert!(matches!(
+            parse_request_url("https://api.ethplorer.io/getTxInfo/0x12").unwrap_err(),
+            UrlParseError::InvalidHash { .. }
+        ));
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getLastBlock/0x0").unwrap_err(),
+            UrlParseError::UnexpectedSegment {
+                route: GET_LAST_BLOCK_ROUTE,
+                segment: "0x0".to_string()
+            }
+        );
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getTop?limit=ten").unwrap_err(),
+            UrlParseError::InvalidParam {
+                name: "limit".to_string(),
+                value: "ten".to_string(),
+                reason: "invalid digit found in string".to_string()
+            }
+        );
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getLastBlock?period=5").unwrap_err(),
+            UrlParseError::UnknownParam {
+                route: GET_LAST_BLOCK_ROUTE,
+                name: "period".to_string()
+            }
+        );
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getTop?limit=1&limit=2").unwrap_err(),
+            UrlParseError::DuplicateParam("limit".to_string())
+        );
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getTokenInfo/0x%G0").unwrap_err(),
+            UrlParseError::InvalidEncoding("0x%G0".to_string())
+        );
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getTop?limit=%+5").unwrap_err(),
+            UrlParseError::InvalidEncoding("%+5".to_string())
+        );
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getTop?limit=%5").unwrap_err(),
+            UrlParseError::InvalidEncoding("%5".to_string())
+        );
+        assert_eq!(
+            parse_request_url("https://api.ethplorer.io/getTokenInfo/0x0").unwrap_err(),
+            UrlParseError::InvalidAddress {
+                value: "0x0".to_string(),
+                reason: HexError::InvalidLength {
+                    expected: 40,
+                    found: 1
+                }
+            }
+        );
+    }
+}
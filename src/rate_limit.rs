@@ -0,0 +1,237 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const SECOND: Duration = Duration::from_secs(1);
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+/// `requests` allowed per `period`, refilled continuously.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl Quota {
+    #[must_use]
+    pub fn per_second(requests: u32) -> Self {
+        Quota {
+            requests,
+            period: SECOND,
+        }
+    }
+
+    #[must_use]
+    pub fn per_hour(requests: u32) -> Self {
+        Quota {
+            requests,
+            period: HOUR,
+        }
+    }
+}
+
+// Ethplorer API plans; the paid tiers only publish a per-second cap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Plan {
+    Freekey,
+    Personal,
+    Basic,
+    Pro,
+    Corporate,
+    Custom(Vec<Quota>),
+}
+
+impl Plan {
+    // `freekey` (or no key at all) gets the shared free tier, anything else a personal key
+    #[must_use]
+    pub fn for_api_key(api_key: &str) -> Self {
+        if api_key.is_empty() || api_key == "freekey" {
+            Plan::Freekey
+        } else {
+            Plan::Personal
+        }
+    }
+
+    #[must_use]
+    pub fn quotas(&self) -> Vec<Quota> {
+        match self {
+            Plan::Freekey => vec![Quota::per_second(2), Quota::per_hour(200)],
+            Plan::Personal => vec![Quota::per_second(5), Quota::per_hour(5000)],
+            Plan::Basic => vec![Quota::per_second(10)],
+            Plan::Pro => vec![Quota::per_second(30)],
+            Plan::Corporate => vec![Quota::per_second(50)],
+            Plan::Custom(quotas) => quotas.clone(),
+        }
+    }
+
+    // Largest `limit` param the plan accepts
+    #[must_use]
+    pub fn max_limit(&self) -> u64 {
+        match self {
+            Plan::Freekey => 100,
+            _ => 1000,
+        }
+    }
+
+    // Longest `period` param, in days, the plan accepts
+    #[must_use]
+    pub fn max_period(&self) -> u64 {
+        match self {
+            Plan::Freekey => 30,
+            _ => 90,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    per_second: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(quota: Quota, now: Instant) -> Self {
+        let capacity = f64::from(quota.requests.max(1));
+        Bucket {
+            capacity,
+            tokens: capacity,
+            per_second: capacity / quota.period.as_secs_f64().max(f64::EPSILON),
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.updated = now;
+    }
+
+    fn wait(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            return Duration::from_secs(0);
+        }
+        Duration::from_secs_f64((1.0 - self.tokens) / self.per_second)
+    }
+}
+
+/// Token-bucket limiter enforcing every quota of a `Plan`. Clones share the same
+/// buckets, so one limiter can pace several clients, threads and tasks.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    buckets: Arc<Mutex<Vec<Bucket>>>,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn new(plan: &Plan) -> Self {
+        let now = Instant::now();
+        let buckets = plan
+            .quotas()
+            .into_iter()
+            .map(|quota| Bucket::new(quota, now))
+            .collect();
+        RateLimiter {
+            buckets: Arc::new(Mutex::new(buckets)),
+        }
+    }
+
+    /// Takes a request slot if every quota has one free.
+    ///
+    /// # Errors
+    /// Returns how long to wait before a slot frees up; nothing is consumed.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        for bucket in buckets.iter_mut() {
+            bucket.refill(now);
+        }
+        let wait = buckets.iter().map(Bucket::wait).max().unwrap_or_default();
+        if wait > Duration::from_secs(0) {
+            return Err(wait);
+        }
+        for bucket in buckets.iter_mut() {
+            bucket.tokens -= 1.0;
+        }
+        Ok(())
+    }
+
+    // Blocks the current thread until a slot is free
+    pub fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            thread::sleep(wait);
+        }
+    }
+
+    // Waits for a slot without blocking the executor
+    #[cfg(feature = "async")]
+    pub async fn acquire_async(&self) {
+        while let Err(wait) = self.try_acquire() {
+            futures_timer::Delay::new(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_for_api_key_works() {
+        assert_eq!(Plan::for_api_key(""), Plan::Freekey);
+        assert_eq!(Plan::for_api_key("freekey"), Plan::Freekey);
+        assert_eq!(Plan::for_api_key("EK-abc"), Plan::Personal);
+        assert_eq!(Plan::Freekey.max_limit(), 100);
+        assert_eq!(Plan::Pro.max_period(), 90);
+        assert_eq!(
+            Plan::Custom(vec![Quota::per_second(1)]).quotas(),
+            vec![Quota::per_second(1)]
+        );
+    }
+
+    #[test]
+    fn rate_limiter_enforces_every_quota() {
+        let limiter = RateLimiter::new(&Plan::Custom(vec![
+            Quota::per_second(3),
+            Quota::per_hour(2),
+        ]));
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.clone().try_acquire().is_ok());
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait > SECOND && wait <= HOUR);
+    }
+
+    #[test]
+    fn rate_limiter_acquire_waits_for_refill() {
+        let limiter = RateLimiter::new(&Plan::Custom(vec![Quota {
+            requests: 1,
+            period: Duration::from_millis(50),
+        }]));
+        let start = Instant::now();
+        limiter.acquire();
+        limiter.acquire();
+        assert!(start.elapsed() >= Duration::from_millis(40));
+
+        let shared = limiter.clone();
+        let handle = thread::spawn(move || shared.acquire());
+        limiter.acquire();
+        handle.join().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(120));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn rate_limiter_acquire_async_works() {
+        let limiter = RateLimiter::new(&Plan::Custom(vec![Quota {
+            requests: 1,
+            period: Duration::from_millis(30),
+        }]));
+        let start = Instant::now();
+        futures::executor::block_on(async {
+            limiter.acquire_async().await;
+            limiter.acquire_async().await;
+        });
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
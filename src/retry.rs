@@ -0,0 +1,224 @@
+use crate::error::{ApiErrorCode, EthplorerError};
+use crate::transport::HttpResponse;
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// How a client re-sends idempotent requests that failed for a transient reason.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    // Total tries including the first one
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: bool,
+    pub respect_retry_after: bool,
+    pub retryable_codes: Vec<ApiErrorCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+            retryable_codes: vec![ApiErrorCode::LimitExceeded],
+        }
+    }
+}
+
+impl RetryPolicy {
+    #[must_use]
+    pub fn new() -> Self {
+        RetryPolicy::default()
+    }
+
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    #[must_use]
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    #[must_use]
+    pub fn with_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    #[must_use]
+    pub fn with_retryable_codes(mut self, codes: Vec<ApiErrorCode>) -> Self {
+        self.retryable_codes = codes;
+        self
+    }
+
+    // Transport failures, 429, 5xx and the configured API error codes are transient
+    #[must_use]
+    pub fn is_retryable(&self, err: &EthplorerError) -> bool {
+        match err {
+            EthplorerError::Transport(_) => true,
+            EthplorerError::Status { status, .. } => *status == 429 || *status >= 500,
+            EthplorerError::Api(err) => self.retryable_codes.contains(&err.code),
+            _ => false,
+        }
+    }
+
+    // Delay before attempt `attempt + 1`, with the backoff capped at `max_delay`. A
+    // `Retry-After` hint replaces the backoff and is waited out in full, or, when it's
+    // past `max_delay`, yields `None` so the request is given up on instead
+    #[must_use]
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if let Some(retry_after) = retry_after.filter(|_| self.respect_retry_after) {
+            return Some(retry_after).filter(|retry_after| *retry_after <= self.max_delay);
+        }
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+        let backoff = self
+            .base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        if self.jitter {
+            // Anywhere between half and the full backoff
+            let half = backoff / 2;
+            Some(half + half.mul_f64(random_unit()))
+        } else {
+            Some(backoff)
+        }
+    }
+
+    pub(crate) fn should_retry(
+        &self,
+        method_idempotent: bool,
+        attempt: u32,
+        err: &EthplorerError,
+    ) -> bool {
+        method_idempotent && attempt < self.max_attempts && self.is_retryable(err)
+    }
+}
+
+// Uniform-ish value in [0, 1) from std's randomly keyed hasher, enough for jitter
+fn random_unit() -> f64 {
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    #[allow(clippy::cast_precision_loss)]
+    let unit = bits as f64 / (1_u64 << 53) as f64;
+    unit
+}
+
+// `Retry-After` is either delay seconds or an HTTP date
+pub(crate) fn retry_after(response: &HttpResponse) -> Option<Duration> {
+    let value = response.header("retry-after")?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
+
+pub(crate) fn with_attempts(err: EthplorerError, attempts: u32) -> EthplorerError {
+    if attempts > 1 {
+        EthplorerError::Retried {
+            attempts,
+            last: Box::new(err),
+            retry_after: None,
+        }
+    } else {
+        err
+    }
+}
+
+// The server's `Retry-After` is longer than the policy is willing to wait
+pub(crate) fn gave_up(
+    err: EthplorerError,
+    attempts: u32,
+    retry_after: Option<Duration>,
+) -> EthplorerError {
+    EthplorerError::Retried {
+        attempts,
+        last: Box::new(err),
+        retry_after,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+
+    #[test]
+    fn retry_policy_classifies_errors() {
+        let policy = RetryPolicy::new();
+        let status = |status| EthplorerError::Status {
+            status,
+            body: String::new(),
+        };
+        let api = |code| {
+            EthplorerError::Api(ApiError {
+                code,
+                message: String::new(),
+            })
+        };
+        assert!(policy.is_retryable(&status(503)));
+        assert!(policy.is_retryable(&status(429)));
+        assert!(!policy.is_retryable(&status(404)));
+        assert!(policy.is_retryable(&api(ApiErrorCode::LimitExceeded)));
+        assert!(!policy.is_retryable(&api(ApiErrorCode::InvalidAddress)));
+        assert!(!policy.should_retry(false, 1, &status(503)));
+        assert!(!policy.should_retry(true, 3, &status(503)));
+    }
+
+    #[test]
+    fn retry_policy_delay_works() {
+        let policy = RetryPolicy::new()
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(300))
+            .with_jitter(false);
+        assert_eq!(policy.delay(1, None), Some(Duration::from_millis(100)));
+        assert_eq!(policy.delay(2, None), Some(Duration::from_millis(200)));
+        assert_eq!(policy.delay(3, None), Some(Duration::from_millis(300)));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(10))), None);
+        assert_eq!(
+            policy.delay(3, Some(Duration::from_millis(250))),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            policy
+                .clone()
+                .with_retry_after(false)
+                .delay(1, Some(Duration::from_secs(10))),
+            Some(Duration::from_millis(100))
+        );
+
+        let jittered = policy.with_jitter(true).delay(2, None).unwrap();
+        assert!(jittered >= Duration::from_millis(100) && jittered <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn retry_after_header_works() {
+        let response = HttpResponse::new(429, "").with_header("Retry-After", "7");
+        assert_eq!(retry_after(&response), Some(Duration::from_secs(7)));
+        let response =
+            HttpResponse::new(503, "").with_header("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(retry_after(&response), None);
+        assert_eq!(retry_after(&HttpResponse::new(503, "")), None);
+    }
+}
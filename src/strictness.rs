@@ -0,0 +1,187 @@
+use crate::config::LimitViolation;
+use serde::de;
+use serde::de::DeserializeOwned;
+use std::cell::RefCell;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+
+// How deserializers treat values they can only make sense of by guessing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strictness {
+    // Falls back to a default and records a `ParseWarning`
+    #[default]
+    Lenient,
+    // Fails the whole response, naming the offending value
+    Strict,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    pub value: String,
+    pub reason: String,
+}
+
+impl ParseWarning {
+    #[must_use]
+    pub fn new(value: &str, reason: &str) -> Self {
+        ParseWarning {
+            value: value.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {:?}", self.reason, self.value)
+    }
+}
+
+/// A deserialized response along with the warnings its lenient parse recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parsed<T> {
+    pub value: T,
+    pub warnings: Vec<ParseWarning>,
+    // Params of the request past the plan's limits, sent anyway under `LimitPolicy::Warn`
+    pub limit_violations: Vec<LimitViolation>,
+}
+
+impl<T> Parsed<T> {
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    #[must_use]
+    pub fn with_limit_violations(mut self, limit_violations: Vec<LimitViolation>) -> Self {
+        self.limit_violations = limit_violations;
+        self
+    }
+
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty() && self.limit_violations.is_empty()
+    }
+}
+
+impl<T> Deref for Parsed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: DeserializeOwned> Parsed<T> {
+    /// Deserializes `body` under `strictness`, collecting warnings.
+    ///
+    /// # Errors
+    /// Fails when the body is not a valid `T`, or holds a guessed value under `Strictness::Strict`.
+    pub fn from_slice(body: &[u8], strictness: Strictness) -> Result<Self, serde_json::Error> {
+        let (result, warnings) = scoped(strictness, || serde_json::from_slice(body));
+        result.map(|value| Parsed {
+            value,
+            warnings,
+            limit_violations: vec![],
+        })
+    }
+}
+
+struct Context {
+    strictness: Strictness,
+    warnings: Vec<ParseWarning>,
+}
+
+// serde offers no way to hand state to a deserializer, so the settings of the
+// parse running on this thread live here
+thread_local! {
+    static CONTEXT: RefCell<Option<Context>> = const { RefCell::new(None) };
+}
+
+// Restores the enclosing context even if the parse panics
+struct Scope {
+    previous: Option<Context>,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CONTEXT.with(|context| context.replace(previous));
+    }
+}
+
+// Runs `parse` under `strictness` and returns what it produced with its warnings
+pub(crate) fn scoped<R>(
+    strictness: Strictness,
+    parse: impl FnOnce() -> R,
+) -> (R, Vec<ParseWarning>) {
+    let context = Context {
+        strictness,
+        warnings: vec![],
+    };
+    let scope = Scope {
+        previous: CONTEXT.with(|current| current.replace(Some(context))),
+    };
+    let result = parse();
+    let warnings = CONTEXT
+        .with(|current| current.borrow_mut().take())
+        .map(|context| context.warnings)
+        .unwrap_or_default();
+    drop(scope);
+    (result, warnings)
+}
+
+// Called by deserializers on a value they can't take at face value. Outside of
+// `scoped`, e.g. a plain `serde_json::from_str`, parsing stays lenient and silent.
+pub(crate) fn recover<T, E: de::Error>(fallback: T, warning: ParseWarning) -> Result<T, E> {
+    CONTEXT.with(|current| match current.borrow_mut().as_mut() {
+        Some(context) if context.strictness == Strictness::Strict => Err(E::custom(&warning)),
+        Some(context) => {
+            context.warnings.push(warning);
+            Ok(fallback)
+        }
+        None => Ok(fallback),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Deserializer};
+
+    #[derive(Debug, PartialEq)]
+    struct Guessed(u64);
+
+    impl<'de> Deserialize<'de> for Guessed {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let value = String::deserialize(deserializer)?;
+            match value.parse() {
+                Ok(number) => Ok(Guessed(number)),
+                Err(_) => recover(Guessed(0), ParseWarning::new(&value, "not a number")),
+            }
+        }
+    }
+
+    #[test]
+    fn parsed_collects_warnings_when_lenient() {
+        let parsed: Parsed<Vec<Guessed>> =
+            Parsed::from_slice(br#"["1", "x", "y"]"#, Strictness::Lenient).unwrap();
+        assert_eq!(*parsed, vec![Guessed(1), Guessed(0), Guessed(0)]);
+        assert_eq!(parsed.warnings.len(), 2);
+        assert_eq!(parsed.warnings[0].to_string(), r#"not a number "x""#);
+
+        let clean: Parsed<Vec<Guessed>> = Parsed::from_slice(b"[]", Strictness::Lenient).unwrap();
+        assert!(clean.is_clean());
+
+        let plain: Vec<Guessed> = serde_json::from_str(r#"["x"]"#).unwrap();
+        assert_eq!(plain, vec![Guessed(0)]);
+    }
+
+    #[test]
+    fn parsed_fails_when_strict() {
+        let err =
+            Parsed::<Vec<Guessed>>::from_slice(br#"["1", "x"]"#, Strictness::Strict).unwrap_err();
+        assert!(err.to_string().contains(r#"not a number "x""#));
+    }
+}
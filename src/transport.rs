@@ -0,0 +1,461 @@
+use crate::types::RequestConfig;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex, PoisonError};
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    #[must_use]
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        HttpResponse {
+            status,
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    // Header names are case insensitive
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+// Cloneable so one failed request can be reported to every coalesced caller
+#[derive(Debug, Clone)]
+pub struct TransportError {
+    message: String,
+    source: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+impl TransportError {
+    #[must_use]
+    pub fn new(message: &str) -> Self {
+        TransportError {
+            message: message.to_string(),
+            source: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_source(message: &str, source: impl Error + Send + Sync + 'static) -> Self {
+        TransportError {
+            message: message.to_string(),
+            source: Some(Arc::new(source)),
+        }
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for TransportError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.source {
+            Some(source) => write!(f, "{}: {}", self.message, source),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for TransportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source.as_ref() as &(dyn Error + 'static))
+    }
+}
+
+/// Sends a `RequestConfig` and hands back the raw response.
+pub trait Transport {
+    /// # Errors
+    /// Returns a `TransportError` when no HTTP response could be obtained.
+    fn send(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError>;
+}
+
+/// Async counterpart of `Transport`.
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncTransport {
+    /// # Errors
+    /// Returns a `TransportError` when no HTTP response could be obtained.
+    async fn send(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError>;
+}
+
+/// In-memory transport that serves canned responses keyed by the request URL
+/// (`RequestConfig`'s `Display`) and records every request it receives. Several
+/// responses registered for one URL are served in order, the last one repeating.
+#[derive(Debug, Default)]
+pub struct MemoryTransport {
+    responses: Mutex<HashMap<String, VecDeque<HttpResponse>>>,
+    fallback: Option<HttpResponse>,
+    requests: Mutex<Vec<RequestConfig>>,
+}
+
+impl MemoryTransport {
+    #[must_use]
+    pub fn new() -> Self {
+        MemoryTransport::default()
+    }
+
+    #[must_use]
+    pub fn with_response(self, url: &str, response: HttpResponse) -> Self {
+        self.responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(url.to_string())
+            .or_default()
+            .push_back(response);
+        self
+    }
+
+    #[must_use]
+    pub fn with_fallback(mut self, response: HttpResponse) -> Self {
+        self.fallback = Some(response);
+        self
+    }
+
+    #[must_use]
+    pub fn requests(&self) -> Vec<RequestConfig> {
+        self.requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn respond(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError> {
+        self.requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(config.clone());
+        let url = config.to_string();
+        let mut responses = self
+            .responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let queued = responses.get_mut(&url).and_then(|queue| {
+            if queue.len() > 1 {
+                queue.pop_front()
+            } else {
+                queue.front().cloned()
+            }
+        });
+        queued
+            .or_else(|| self.fallback.clone())
+            .ok_or_else(|| TransportError::new(&format!("no response registered for {url}")))
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn send(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError> {
+        self.respond(config)
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncTransport for MemoryTransport {
+    async fn send(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError> {
+        self.respond(config)
+    }
+}
+
+#[cfg(feature = "blocking")]
+pub use self::blocking::UreqTransport;
+
+#[cfg(feature = "blocking")]
+mod blocking {
+    use super::{HttpResponse, Transport, TransportError, FORM_CONTENT_TYPE};
+    use crate::config::ClientConfig;
+    use crate::types::RequestConfig;
+    use std::error::Error;
+    use std::fmt::Write;
+    use std::io::Read;
+
+    /// Blocking transport backed by `ureq`.
+    #[derive(Debug, Clone)]
+    pub struct UreqTransport {
+        agent: ureq::Agent,
+    }
+
+    impl UreqTransport {
+        #[must_use]
+        pub fn new() -> Self {
+            UreqTransport::from_agent(ureq::AgentBuilder::new().build())
+        }
+
+        // Applies the timeouts carried by the client configuration
+        #[must_use]
+        pub fn from_config(config: &ClientConfig) -> Self {
+            let mut builder = ureq::AgentBuilder::new();
+            if let Some(timeout) = config.timeout {
+                builder = builder.timeout(timeout);
+            }
+            if let Some(timeout) = config.connect_timeout {
+                builder = builder.timeout_connect(timeout);
+            }
+            UreqTransport::from_agent(builder.build())
+        }
+
+        #[must_use]
+        pub fn from_agent(agent: ureq::Agent) -> Self {
+            UreqTransport { agent }
+        }
+    }
+
+    // ureq's error shows the full URL, API key included, so only its kind, message and
+    // cause are kept
+    fn transport_error(config: &RequestConfig, transport: &ureq::Transport) -> TransportError {
+        let mut message = format!(
+            "request to {} failed: {}",
+            config.redacted_url(),
+            transport.kind()
+        );
+        if let Some(detail) = transport.message() {
+            let _ = write!(message, ": {detail}");
+        }
+        if let Some(cause) = Error::source(transport) {
+            let _ = write!(message, ": {cause}");
+        }
+        TransportError::new(&message)
+    }
+
+    impl Default for UreqTransport {
+        fn default() -> Self {
+            UreqTransport::new()
+        }
+    }
+
+    impl Transport for UreqTransport {
+        fn send(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError> {
+            let request = self.agent.request(config.method.as_str(), &config.url());
+            let sent = match config.form_body() {
+                Some(body) => request
+                    .set("Content-Type", FORM_CONTENT_TYPE)
+                    .send_string(&body),
+                None => request.call(),
+            };
+            let response = match sent {
+                Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+                Err(ureq::Error::Transport(transport)) => {
+                    return Err(transport_error(config, &transport));
+                }
+            };
+
+            let status = response.status();
+            let headers = response
+                .headers_names()
+                .into_iter()
+                .filter_map(|name| {
+                    let value = response.header(&name)?.to_string();
+                    Some((name, value))
+                })
+                .collect();
+
+            let mut body = vec![];
+            response
+                .into_reader()
+                .read_to_end(&mut body)
+                .map_err(|err| TransportError::with_source("failed to read body", err))?;
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::asynchronous::ReqwestTransport;
+
+#[cfg(feature = "async")]
+mod asynchronous {
+    use super::{AsyncTransport, HttpResponse, TransportError, FORM_CONTENT_TYPE};
+    use crate::config::ClientConfig;
+    use crate::types::{Method, RequestConfig};
+
+    /// Async transport backed by `reqwest`.
+    #[derive(Debug, Clone, Default)]
+    pub struct ReqwestTransport {
+        client: reqwest::Client,
+    }
+
+    impl ReqwestTransport {
+        #[must_use]
+        pub fn new() -> Self {
+            ReqwestTransport::default()
+        }
+
+        /// Applies the timeouts carried by the client configuration.
+        ///
+        /// # Errors
+        /// Fails when `reqwest` cannot initialise its TLS backend.
+        pub fn from_config(config: &ClientConfig) -> Result<Self, TransportError> {
+            let mut builder = reqwest::Client::builder();
+            if let Some(timeout) = config.timeout {
+                builder = builder.timeout(timeout);
+            }
+            if let Some(timeout) = config.connect_timeout {
+                builder = builder.connect_timeout(timeout);
+            }
+            let client = builder
+                .build()
+                .map_err(|err| TransportError::with_source("failed to build client", err))?;
+            Ok(ReqwestTransport::from_client(client))
+        }
+
+        #[must_use]
+        pub fn from_client(client: reqwest::Client) -> Self {
+            ReqwestTransport { client }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AsyncTransport for ReqwestTransport {
+        async fn send(&self, config: &RequestConfig) -> Result<HttpResponse, TransportError> {
+            let method = match config.method {
+                Method::Get => reqwest::Method::GET,
+                Method::Post => reqwest::Method::POST,
+            };
+            let mut request = self.client.request(method, config.url());
+            if let Some(body) = config.form_body() {
+                request = request
+                    .header(reqwest::header::CONTENT_TYPE, FORM_CONTENT_TYPE)
+                    .body(body);
+            }
+            // `without_url` keeps the API key out of the error's message
+            let response = request.send().await.map_err(|err| {
+                let message = format!("request to {} failed", config.redacted_url());
+                TransportError::with_source(&message, err.without_url())
+            })?;
+
+            let status = response.status().as_u16();
+            let headers = response
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    let value = value.to_str().ok()?.to_string();
+                    Some((name.to_string(), value))
+                })
+                .collect();
+
+            let body = response
+                .bytes()
+                .await
+                .map_err(|err| {
+                    TransportError::with_source("failed to read body", err.without_url())
+                })?
+                .to_vec();
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_last_block_config, ClientConfig};
+
+    #[test]
+    fn http_response_header_is_case_insensitive() {
+        let response = HttpResponse::new(200, "{}").with_header("Retry-After", "5");
+        assert_eq!(response.header("retry-after"), Some("5"));
+        assert_eq!(response.header("x-missing"), None);
+        assert!(response.is_success());
+        assert!(!HttpResponse::new(404, "").is_success());
+    }
+
+    #[test]
+    fn memory_transport_works() {
+        let transport = MemoryTransport::new().with_response(
+            "https://api.ethplorer.io/getLastBlock",
+            HttpResponse::new(200, r#"{"lastBlock":1}"#),
+        );
+        let config = get_last_block_config(&ClientConfig::default());
+        let response = Transport::send(&transport, &config).unwrap();
+        assert_eq!(response.body, br#"{"lastBlock":1}"#.to_vec());
+        assert_eq!(transport.requests(), vec![config.clone()]);
+
+        let queued = MemoryTransport::new()
+            .with_response(
+                "https://api.ethplorer.io/getLastBlock",
+                HttpResponse::new(503, ""),
+            )
+            .with_response(
+                "https://api.ethplorer.io/getLastBlock",
+                HttpResponse::new(200, "{}"),
+            );
+        let statuses: Vec<u16> = (0..3)
+            .map(|_| Transport::send(&queued, &config).unwrap().status)
+            .collect();
+        assert_eq!(statuses, vec![503, 200, 200]);
+
+        let missing = Transport::send(
+            &MemoryTransport::new(),
+            &get_last_block_config(&ClientConfig::default()),
+        );
+        assert!(missing.is_err());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn ureq_errors_leave_out_api_key() {
+        // Nothing listens on the port once the listener is gone
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let config = get_last_block_config(
+            &ClientConfig::new()
+                .with_base_url(&base_url)
+                .with_api_key("secret-key"),
+        );
+
+        let err = Transport::send(&UreqTransport::new(), &config).unwrap_err();
+        let mut chain = vec![err.to_string()];
+        let mut source = err.source();
+        while let Some(cause) = source {
+            chain.push(cause.to_string());
+            source = cause.source();
+        }
+        assert!(chain[0].contains("apiKey=***"), "{}", chain[0]);
+        assert!(
+            chain.iter().all(|text| !text.contains("secret-key")),
+            "{:?}",
+            chain
+        );
+    }
+}
@@ -1,149 +1,370 @@
 use std::convert::TryFrom;
+use crate::address::{Address, TxHash};
+use crate::amount::{eth_amount, flexible, AmountError, RawAmount, TokenAmount};
+use crate::drift::{from_slice_keeping_unknown, tracked_from_value, UnknownFields};
+use crate::error::ApiError;
+use crate::strictness::{recover, ParseWarning};
 use chrono::serde::ts_seconds;
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use serde::de::{MapAccess, Visitor};
-use serde::{de, Deserialize, Deserializer};
+use serde::de::DeserializeOwned;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
 use std::fmt;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Display, Formatter, Write};
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::str::FromStr;
-use void::Void;
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Method {
+    #[default]
+    Get,
+    Post,
+}
+
+impl Method {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+        }
+    }
+
+    // Only idempotent requests are safe to send again after a failure
+    #[must_use]
+    pub fn is_idempotent(self) -> bool {
+        self == Method::Get
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct RequestConfig {
     pub network: String,
     pub routes: Vec<String>,
     pub params: Vec<(String, String)>,
+    pub method: Method,
 }
 
 impl Display for RequestConfig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "{}", self.network)?;
         for route in &self.routes {
-            write!(f, "/{}", route)?;
+            write!(f, "/{route}")?;
         }
         write!(f, "")
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+const REDACTED: &str = "***";
+
+// Percent-encodes everything outside the RFC 3986 unreserved set
+fn percent_encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(char::from(byte));
+            }
+            // Writing into a String can't fail
+            _ => {
+                let _ = write!(out, "%{byte:02X}");
+            }
+        }
+    }
+    out
+}
+
+impl RequestConfig {
+    // Full URL including the query string, ready to be sent; a POST carries its
+    // params in `form_body` instead
+    #[must_use]
+    pub fn url(&self) -> String {
+        self.render_url(false)
+    }
+
+    // Same as `url` with the `apiKey` value masked, safe for logs and errors
+    #[must_use]
+    pub fn redacted_url(&self) -> String {
+        self.render_url(true)
+    }
+
+    // `application/x-www-form-urlencoded` body of a POST request
+    #[must_use]
+    pub fn form_body(&self) -> Option<String> {
+        match self.method {
+            Method::Get => None,
+            Method::Post => Some(self.render_params(false)),
+        }
+    }
+
+    // Canonical identity of a request: params sorted and `apiKey` dropped, so the
+    // same call made with another key or param order maps to the same string
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        let mut params: Vec<(String, String)> = self
+            .params
+            .iter()
+            .filter(|(key, _)| key != "apiKey")
+            .cloned()
+            .collect();
+        params.sort();
+        let canonical = RequestConfig {
+            params,
+            ..self.clone()
+        };
+        match canonical.form_body() {
+            Some(body) => format!("{} {} {}", self.method.as_str(), canonical.url(), body),
+            None => format!("{} {}", self.method.as_str(), canonical.url()),
+        }
+    }
+
+    fn render_url(&self, redact: bool) -> String {
+        let mut url = self.network.clone();
+        for route in &self.routes {
+            url.push('/');
+            url.push_str(&percent_encode(route));
+        }
+
+        if self.method == Method::Get && !self.params.is_empty() {
+            url.push('?');
+            url.push_str(&self.render_params(redact));
+        }
+        url
+    }
+
+    fn render_params(&self, redact: bool) -> String {
+        let mut out = String::new();
+        for (key, value) in &self.params {
+            let value = if redact && key == "apiKey" {
+                REDACTED.to_string()
+            } else {
+                percent_encode(value)
+            };
+            if !out.is_empty() {
+                out.push('&');
+            }
+            out.push_str(&percent_encode(key));
+            out.push('=');
+            out.push_str(&value);
+        }
+        out
+    }
+}
+
+// Either a successful body or Ethplorer's error envelope
+#[derive(Debug)]
+pub enum ApiResponse<T> {
+    Ok(T),
+    Error(ApiError),
+}
+
+#[derive(Deserialize)]
+struct ErrorEnvelope {
+    error: ApiError,
+}
+
+impl<T: DeserializeOwned> ApiResponse<T> {
+    /// # Errors
+    /// Fails when the body is neither an error envelope nor a valid `T`.
+    pub fn from_slice(body: &[u8]) -> Result<Self, serde_json::Error> {
+        if let Ok(envelope) = serde_json::from_slice::<ErrorEnvelope>(body) {
+            return Ok(ApiResponse::Error(envelope.error));
+        }
+        serde_json::from_slice(body).map(ApiResponse::Ok)
+    }
+
+    /// Like `from_slice`, keeping unknown keys in each struct's `unknown` field.
+    ///
+    /// # Errors
+    /// Fails when the body is neither an error envelope nor a valid `T`.
+    pub fn from_slice_keeping_unknown(body: &[u8]) -> Result<Self, serde_json::Error> {
+        if let Ok(envelope) = serde_json::from_slice::<ErrorEnvelope>(body) {
+            return Ok(ApiResponse::Error(envelope.error));
+        }
+        from_slice_keeping_unknown(body).map(ApiResponse::Ok)
+    }
+
+    /// # Errors
+    /// Returns the `ApiError` carried by an error envelope.
+    pub fn into_result(self) -> Result<T, ApiError> {
+        match self {
+            ApiResponse::Ok(value) => Ok(value),
+            ApiResponse::Error(err) => Err(err),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct LastBlock {
-    #[serde(rename(deserialize = "lastBlock"))]
+    #[serde(rename = "lastBlock", deserialize_with = "flexible::deserialize")]
     pub last_block: u64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Holder {
-    pub address: String,
-    pub balance: f64,
+    pub address: Address,
+    pub balance: RawAmount,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub share: f64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TopTokenHolders {
     pub holders: Vec<Holder>,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TransactionDate {
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub year: u64,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub month: u64,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub day: u64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct CountTxs {
-    #[serde(rename(deserialize = "_id"))]
+    #[serde(rename = "_id")]
     pub id: TransactionDate,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub ts: u64,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub cnt: u64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TokenDailyTransactionCounts {
-    #[serde(rename(deserialize = "countTxs"))]
+    #[serde(rename = "countTxs")]
     pub count_txs: Vec<CountTxs>,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct TokenPrice {
-    #[serde(default)]
+    // Required, so a price object without a rate is `Pricing::Malformed` rather than zero
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub rate: f64,
     #[serde(default)]
     pub currency: String,
-    #[serde(default)]
+    #[serde(deserialize_with = "flexible::deserialize", default)]
     pub diff: f64,
-    #[serde(default)]
+    #[serde(deserialize_with = "flexible::deserialize", default)]
     pub diff7d: f64,
-    #[serde(default)]
+    #[serde(deserialize_with = "flexible::deserialize", default)]
     pub diff30d: f64,
-    #[serde(rename(deserialize = "volume24h"), default)]
+    #[serde(
+        rename = "volume24h",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub volume_24h: f64,
-    #[serde(rename(deserialize = "volDiff1"), default)]
+    #[serde(
+        rename = "volDiff1",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub volume_diff1: f64,
-    #[serde(rename(deserialize = "volDiff7"), default)]
+    #[serde(
+        rename = "volDiff7",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub volume_diff7: f64,
-    #[serde(rename(deserialize = "volDiff30"), default)]
+    #[serde(
+        rename = "volDiff30",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub volume_diff30: f64,
-    #[serde(rename(deserialize = "marketCapUsd"), default)]
+    #[serde(
+        rename = "marketCapUsd",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub market_cap_usd: f64,
-    #[serde(rename(deserialize = "availableSupply"), default)]
+    #[serde(
+        rename = "availableSupply",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub available_supply: f64,
-    #[serde(default)]
+    #[serde(deserialize_with = "flexible::deserialize", default)]
     pub ts: u64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-impl FromStr for TokenPrice {
-    // This implementation of `from_str` can never fail, so use the impossible
-    // `Void` type as the error type.
-    type Err = Void;
-
-    fn from_str(_: &str) -> Result<Self, Self::Err> {
-        Ok(TokenPrice {
-            ..TokenPrice::default()
-        })
-    }
+// `price` of a token or of ETH. Ethplorer sends `false` when it has no market data,
+// which must not be mistaken for a price of zero.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Pricing {
+    Priced(TokenPrice),
+    #[default]
+    Unpriced,
+    // Neither `false` nor a price object; the raw value is kept for inspection
+    Malformed(serde_json::Value),
 }
 
-fn string_or_struct<'de, T, D>(deserializer: D) -> Result<T, D::Error>
-where
-    T: Deserialize<'de> + FromStr<Err = Void>,
-    D: Deserializer<'de>,
-{
-    struct StringOrStruct<T>(PhantomData<fn() -> T>);
+impl Pricing {
+    #[must_use]
+    pub fn price(&self) -> Option<&TokenPrice> {
+        match self {
+            Pricing::Priced(price) => Some(price),
+            _ => None,
+        }
+    }
 
-    impl<'de, T> Visitor<'de> for StringOrStruct<T>
-    where
-        T: Deserialize<'de> + FromStr<Err = Void>,
-    {
-        type Value = T;
+    #[must_use]
+    pub fn rate(&self) -> Option<f64> {
+        self.price().map(|price| price.rate)
+    }
 
-        fn expecting(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
-            fmtr.write_str("bool or map")
-        }
+    #[must_use]
+    pub fn is_priced(&self) -> bool {
+        self.price().is_some()
+    }
+}
 
-        fn visit_bool<E>(self, _: bool) -> Result<T, E>
-        where
-            E: de::Error,
-        {
-            Ok(FromStr::from_str("").unwrap())
-        }
+impl<'de> Deserialize<'de> for Pricing {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let pricing = match value {
+            serde_json::Value::Bool(false) | serde_json::Value::Null => Pricing::Unpriced,
+            serde_json::Value::Object(_) => match tracked_from_value(&value) {
+                Ok(price) => Pricing::Priced(price),
+                Err(_) => Pricing::Malformed(value),
+            },
+            other => Pricing::Malformed(other),
+        };
+        Ok(pricing)
+    }
+}
 
-        fn visit_map<M>(self, map: M) -> Result<T, M::Error>
-        where
-            M: MapAccess<'de>,
-        {
-            // `MapAccessDeserializer` is a wrapper that turns a `MapAccess`
-            // into a `Deserializer`, allowing it to be used as the input to T's
-            // `Deserialize` implementation. T then deserializes itself using
-            // the entries from the map visitor.
-            Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))
+impl Serialize for Pricing {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Pricing::Priced(price) => price.serialize(serializer),
+            Pricing::Unpriced => serializer.serialize_bool(false),
+            Pricing::Malformed(value) => value.serialize(serializer),
         }
     }
-    deserializer.deserialize_any(StringOrStruct(PhantomData))
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(transparent)]
 pub struct Prices(Vec<Price>);
 
@@ -155,40 +376,68 @@ impl Deref for Prices {
     }
 }
 
-fn num_from_str<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+fn num_to_str<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
 where
-    T: FromStr,
-    T::Err: Display,
-    D: Deserializer<'de>,
+    T: Display,
+    S: Serializer,
 {
-    let s = String::deserialize(deserializer)?;
-    T::from_str(&s).map_err(de::Error::custom)
+    serializer.collect_str(value)
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TokenInfo {
-    pub address: String,
+    pub address: Address,
     pub name: String,
-    #[serde(deserialize_with = "num_from_str", default)]
+    // Accepted as a number or a string but always written as a string, the encoding
+    // Ethplorer uses for most tokens
+    #[serde(
+        deserialize_with = "flexible::deserialize",
+        serialize_with = "num_to_str",
+        default
+    )]
     pub decimals: u64,
     pub symbol: String,
-    #[serde(rename(deserialize = "totalSupply"), default)]
-    pub total_supply: String,
+    #[serde(rename = "totalSupply", default)]
+    pub total_supply: RawAmount,
     #[serde(default)]
     pub owner: String,
-    #[serde(default)]
+    #[serde(
+        rename = "txsCount",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub txs_count: u64,
-    #[serde(rename(deserialize = "transfersCount"), default)]
+    #[serde(
+        rename = "transfersCount",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub transfers_count: u64,
-    #[serde(rename(deserialize = "lastUpdated"), default)]
+    #[serde(
+        rename = "lastUpdated",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub last_updated: u64,
-    #[serde(default)]
+    #[serde(deserialize_with = "flexible::deserialize", default)]
     pub slot: u64,
-    #[serde(rename(deserialize = "StorageTotalSupply"), default)]
+    #[serde(
+        rename = "StorageTotalSupply",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub storage_total_supply: u64,
-    #[serde(rename(deserialize = "issuancesCount"), default)]
+    #[serde(
+        rename = "issuancesCount",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub issuances_count: u64,
-    #[serde(rename(deserialize = "holdersCount"), default)]
+    #[serde(
+        rename = "holdersCount",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub holders_count: u64,
     #[serde(default)]
     pub image: String,
@@ -206,56 +455,116 @@ pub struct TokenInfo {
     pub facebook: String,
     #[serde(default)]
     pub coingecko: String,
-    #[serde(rename(deserialize = "ethTransfersCount"), default)]
+    #[serde(
+        rename = "ethTransfersCount",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub eth_transfer_count: u64,
-    #[serde(deserialize_with = "string_or_struct", default)]
-    pub price: TokenPrice,
-    #[serde(rename(deserialize = "countOps"), default)]
+    #[serde(default)]
+    pub price: Pricing,
+    #[serde(
+        rename = "countOps",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub count_ops: u64,
-    #[serde(rename(deserialize = "publicTags"), default)]
+    #[serde(rename = "publicTags", default)]
     pub public_tags: Vec<String>,
-    #[serde(rename(deserialize = "opCount"), default)]
+    #[serde(
+        rename = "opCount",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub op_count: u64,
-    #[serde(default)]
+    #[serde(deserialize_with = "flexible::deserialize", default)]
     pub added: u64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+impl TokenInfo {
+    /// Pairs a raw amount of this token with its decimals.
+    ///
+    /// # Errors
+    /// Fails when the token reports more decimals than 256 bits can hold.
+    pub fn amount(&self, raw: RawAmount) -> Result<TokenAmount, AmountError> {
+        let decimals = u8::try_from(self.decimals)
+            .map_err(|_| AmountError::DecimalsOutOfRange(self.decimals))?;
+        TokenAmount::new(raw, decimals)
+    }
+
+    /// # Errors
+    /// Fails when the token reports more decimals than 256 bits can hold.
+    pub fn total_supply_amount(&self) -> Result<TokenAmount, AmountError> {
+        self.amount(self.total_supply)
+    }
+
+    // When Ethplorer started tracking the token, set on `getTokensNew` entries
+    #[must_use]
+    pub fn added_at(&self) -> Option<Timestamp> {
+        let seconds = i64::try_from(self.added)
+            .ok()
+            .filter(|&seconds| seconds > 0)?;
+        Utc.timestamp_opt(seconds, 0).single().map(Timestamp)
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct Token {
-    #[serde(rename(deserialize = "tokenInfo"))]
+    #[serde(rename = "tokenInfo")]
     pub token_info: TokenInfo,
     // TokenFinancials
-    pub balance: f64,
-    #[serde(rename(deserialize = "rawBalance"), default)]
-    pub raw_balance: String,
-    #[serde(rename(deserialize = "totalIn"))]
-    pub total_in: f64,
-    #[serde(rename(deserialize = "totalOut"))]
-    pub total_out: f64,
+    pub balance: RawAmount,
+    #[serde(rename = "rawBalance", default)]
+    pub raw_balance: RawAmount,
+    #[serde(rename = "totalIn")]
+    pub total_in: RawAmount,
+    #[serde(rename = "totalOut")]
+    pub total_out: RawAmount,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+impl Token {
+    /// # Errors
+    /// Fails when the token reports more decimals than 256 bits can hold.
+    pub fn balance_amount(&self) -> Result<TokenAmount, AmountError> {
+        self.token_info.amount(self.balance)
+    }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct ETH {
-    #[serde(deserialize_with = "string_or_struct")]
-    pub price: TokenPrice,
-    // TokenFinancials
-    pub balance: f64,
-    #[serde(rename(deserialize = "rawBalance"))]
-    pub raw_balance: String,
-    #[serde(rename(deserialize = "totalIn"))]
-    pub total_in: f64,
-    #[serde(rename(deserialize = "totalOut"))]
-    pub total_out: f64,
+    pub price: Pricing,
+    // TokenFinancials, in ether rather than wei
+    #[serde(with = "eth_amount")]
+    pub balance: TokenAmount,
+    #[serde(rename = "rawBalance")]
+    pub raw_balance: RawAmount,
+    // Only sent with `show_eth_totals`
+    #[serde(rename = "totalIn", with = "eth_amount", default)]
+    pub total_in: TokenAmount,
+    #[serde(rename = "totalOut", with = "eth_amount", default)]
+    pub total_out: TokenAmount,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct ContractInfo {
-    pub creator_hash: String,
-    pub transaction_hash: String,
+    #[serde(rename = "creatorAddress")]
+    pub creator_hash: Address,
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: TxHash,
     #[serde(deserialize_with = "date_or_timestamp", default)]
     pub timestamp: Timestamp,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
-#[derive(Deserialize, Debug)]
+
+// Serialized as Unix seconds, the encoding Ethplorer uses for timestamps
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Timestamp(#[serde(with = "ts_seconds")] DateTime<Utc>);
 
 impl Deref for Timestamp {
@@ -266,10 +575,15 @@ impl Deref for Timestamp {
     }
 }
 
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(date: DateTime<Utc>) -> Self {
+        Timestamp(date)
+    }
+}
+
 impl Default for Timestamp {
     fn default() -> Self {
-        let naive = NaiveDateTime::from_timestamp(0, 0);
-        Timestamp(DateTime::from_utc(naive, Utc))
+        Timestamp(Utc.timestamp_opt(0, 0).unwrap())
     }
 }
 
@@ -279,7 +593,7 @@ fn date_or_timestamp<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
 {
     struct DateOrTimestamp(PhantomData<fn() -> Timestamp>);
 
-    impl<'de> Visitor<'de> for DateOrTimestamp {
+    impl Visitor<'_> for DateOrTimestamp {
         type Value = Timestamp;
 
         fn expecting(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
@@ -290,198 +604,1212 @@ fn date_or_timestamp<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
             where
                 E: de::Error,
         {
-            let naive = NaiveDateTime::from_timestamp(v, 0);
-            Ok(Timestamp(DateTime::from_utc(naive, Utc)))
+            match Utc.timestamp_opt(v, 0).single() {
+                Some(date) => Ok(Timestamp(date)),
+                None => out_of_range(&v),
+            }
         }
 
         fn visit_i32<E>(self, v: i32) -> Result<Timestamp, E>
             where
                 E: de::Error,
         {
-            let naive = NaiveDateTime::from_timestamp(i64::from(v), 0);
-            Ok(Timestamp(DateTime::from_utc(naive, Utc)))
+            self.visit_i64(i64::from(v))
         }
 
         fn visit_i16<E>(self, v: i16) -> Result<Timestamp, E>
             where
                 E: de::Error,
         {
-            let naive = NaiveDateTime::from_timestamp(i64::from(v), 0);
-            Ok(Timestamp(DateTime::from_utc(naive, Utc)))
+            self.visit_i64(i64::from(v))
         }
 
         fn visit_i8<E>(self, v: i8) -> Result<Timestamp, E>
             where
                 E: de::Error,
         {
-            let naive = NaiveDateTime::from_timestamp(i64::from(v), 0);
-            Ok(Timestamp(DateTime::from_utc(naive, Utc)))
+            self.visit_i64(i64::from(v))
         }
 
         fn visit_u64<E>(self, v: u64) -> Result<Timestamp, E>
             where
                 E: de::Error,
         {
-            let from = i64::try_from(v);
-            let out = if let Ok(val) = from {
-                val
-            } else {
-                i64::MAX
-            };
-            let naive = NaiveDateTime::from_timestamp(out, 0);
-            Ok(Timestamp(DateTime::from_utc(naive, Utc)))
+            match i64::try_from(v) {
+                Ok(v) => self.visit_i64(v),
+                Err(_) => out_of_range(&v),
+            }
         }
 
         fn visit_u32<E>(self, v: u32) -> Result<Timestamp, E>
             where
                 E: de::Error,
         {
-            let naive = NaiveDateTime::from_timestamp(i64::from(v), 0);
-            Ok(Timestamp(DateTime::from_utc(naive, Utc)))
+            self.visit_i64(i64::from(v))
         }
 
         fn visit_u16<E>(self, v: u16) -> Result<Timestamp, E>
             where
                 E: de::Error,
         {
-            let naive = NaiveDateTime::from_timestamp(i64::from(v), 0);
-            Ok(Timestamp(DateTime::from_utc(naive, Utc)))
+            self.visit_i64(i64::from(v))
         }
 
         fn visit_u8<E>(self, v: u8) -> Result<Timestamp, E>
             where
                 E: de::Error,
         {
-            let naive = NaiveDateTime::from_timestamp(i64::from(v), 0);
-            Ok(Timestamp(DateTime::from_utc(naive, Utc)))
+            self.visit_i64(i64::from(v))
         }
 
         fn visit_str<E>(self, s: &str) -> Result<Timestamp, E>
             where
                 E: de::Error,
         {
-            let date = DateTime::<Utc>::from_str(s);
-            match date {
+            let midnight = NaiveDate::parse_from_str(s, DAY_FORMAT)
+                .ok()
+                .and_then(|day| day.and_hms_opt(0, 0, 0));
+            if let Some(midnight) = midnight {
+                return Ok(Timestamp(Utc.from_utc_datetime(&midnight)));
+            }
+            match DateTime::<Utc>::from_str(s) {
                 Ok(v) => Ok(Timestamp(v)),
-                Err(_) => Ok(Timestamp::default()),
+                Err(_) => recover(Timestamp::default(), ParseWarning::new(s, "unrecognised date")),
             }
         }
     }
+
+    // Seconds chrono can't represent fall back to the epoch
+    fn out_of_range<E: de::Error>(v: &dyn Display) -> Result<Timestamp, E> {
+        recover(
+            Timestamp::default(),
+            ParseWarning::new(&v.to_string(), "timestamp out of range"),
+        )
+    }
+
     deserializer.deserialize_any(DateOrTimestamp(PhantomData))
 }
 
-#[derive(Deserialize, Debug, Default)]
+// `Price.date` is a calendar day rather than a Unix timestamp
+const DAY_FORMAT: &str = "%Y-%m-%d";
+
+fn day_string<S: Serializer>(date: &Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&date.format(DAY_FORMAT))
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct AddressInfo {
-    pub address: String,
-    #[serde(default)]
+    pub address: Address,
+    #[serde(rename = "ETH", default)]
     pub eth: ETH,
-    #[serde(default)]
+    // Only sent for contracts
+    #[serde(rename = "contractInfo", default)]
     pub contract_info: ContractInfo,
-    #[serde(default)]
+    // Only sent for token contracts
+    #[serde(rename = "tokenInfo", default)]
     pub token_info: TokenInfo,
+    #[serde(default)]
     pub tokens: Vec<Token>,
+    // Only sent with `show_txs_count`
+    #[serde(
+        rename = "countTxs",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
+    pub count_txs: u64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Operations {
     #[serde(deserialize_with = "date_or_timestamp")]
     pub timestamp: Timestamp,
-    #[serde(rename(deserialize = "transaction_hash"), default)]
-    pub transaction_hash: String,
-    #[serde(rename(deserialize = "tokenInfo"))]
+    #[serde(rename = "transactionHash", alias = "transaction_hash", default)]
+    pub transaction_hash: TxHash,
+    #[serde(rename = "tokenInfo")]
     pub token_info: TokenInfo,
-    #[serde(rename(deserialize = "type"))]
-    pub op_type: String,
+    #[serde(rename = "type")]
+    pub op_type: OperationType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    pub from: Address,
+    pub to: Address,
     #[serde(default)]
-    pub address: String,
-    pub from: String,
-    pub to: String,
-    #[serde(deserialize_with = "num_from_str", default)]
-    pub value: u128,
+    pub value: RawAmount,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TokenHistory {
     pub operations: Vec<Operations>,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct AddressTransaction {
     #[serde(deserialize_with = "date_or_timestamp")]
     pub timestamp: Timestamp,
-    pub from: String,
-    pub to: String,
-    pub hash: String,
+    pub from: Address,
+    pub to: Address,
+    pub hash: TxHash,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub value: f64,
     pub input: String,
     pub success: bool,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+// Response of `getAddressTransactions`, newest first
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub struct AddressTransactions(Vec<AddressTransaction>);
+
+impl Deref for AddressTransactions {
+    type Target = Vec<AddressTransaction>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for AddressTransactions {
+    type Item = AddressTransaction;
+    type IntoIter = std::vec::IntoIter<AddressTransaction>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TxLog {
+    pub address: Address,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub data: String,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TxInfo {
+    pub hash: TxHash,
+    #[serde(deserialize_with = "date_or_timestamp", default)]
+    pub timestamp: Timestamp,
+    // Missing while the transaction is still pending
+    #[serde(
+        rename = "blockNumber",
+        deserialize_with = "flexible::deserialize_option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub block_number: Option<u64>,
+    #[serde(deserialize_with = "flexible::deserialize", default)]
+    pub confirmations: u64,
+    #[serde(default)]
+    pub success: bool,
+    pub from: Address,
+    // `None` for a contract creation, see `creates`
+    #[serde(default)]
+    pub to: Option<Address>,
+    // In ether rather than wei
+    #[serde(with = "eth_amount", default)]
+    pub value: TokenAmount,
+    #[serde(default)]
+    pub input: String,
+    // Gas figures can be large, so they keep full precision
+    #[serde(rename = "gasLimit", default)]
+    pub gas_limit: RawAmount,
+    #[serde(rename = "gasUsed", default)]
+    pub gas_used: RawAmount,
+    #[serde(rename = "gasPrice", default)]
+    pub gas_price: RawAmount,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creates: Option<Address>,
+    #[serde(default)]
+    pub logs: Vec<TxLog>,
+    // Token transfers made by the transaction
+    #[serde(default)]
+    pub operations: Vec<Operations>,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TopTokens {
     pub tokens: Vec<TokenInfo>,
-    #[serde(rename(deserialize = "opCount"), default)]
+    #[serde(
+        rename = "opCount",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
     pub op_count: u64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+// Response of `getTokensNew`: recently added tokens, see `TokenInfo::added_at`
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub struct TokensNew(Vec<TokenInfo>);
+
+impl Deref for TokensNew {
+    type Target = Vec<TokenInfo>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for TokensNew {
+    type Item = TokenInfo;
+    type IntoIter = std::vec::IntoIter<TokenInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+// Entry of `getTop`; which of the ranking fields are filled depends on the criteria
+#[derive(Serialize, Debug, Default)]
+pub struct TopToken {
+    #[serde(flatten)]
+    pub token_info: TokenInfo,
+    // `trade`: trade volume over the last 24 hours and the 24 hours before
+    pub volume: f64,
+    #[serde(rename = "volumePrevious")]
+    pub volume_previous: f64,
+    // `cap`: market capitalization now and a day ago
+    pub cap: f64,
+    #[serde(rename = "capPrevious")]
+    pub cap_previous: f64,
+    #[serde(rename = "$unknown", skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+// Keys of a `getTop` entry: those of `TokenInfo`, then the ranking fields
+const TOP_TOKEN_FIELDS: &[&str] = &[
+    "address",
+    "name",
+    "decimals",
+    "symbol",
+    "totalSupply",
+    "owner",
+    "txsCount",
+    "transfersCount",
+    "lastUpdated",
+    "slot",
+    "StorageTotalSupply",
+    "issuancesCount",
+    "holdersCount",
+    "image",
+    "description",
+    "website",
+    "telegram",
+    "twitter",
+    "reddit",
+    "facebook",
+    "coingecko",
+    "ethTransfersCount",
+    "price",
+    "countOps",
+    "publicTags",
+    "opCount",
+    "added",
+    "volume",
+    "volumePrevious",
+    "cap",
+    "capPrevious",
+    "$unknown",
+];
+
+// Read as one flat struct instead of through `#[serde(flatten)]`, which would hide
+// the entry's keys from the drift tracker and leave `unknown` empty
+impl<'de> Deserialize<'de> for TopToken {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("TopToken", TOP_TOKEN_FIELDS, TopTokenVisitor)
+    }
+}
+
+struct TopTokenVisitor;
+
+impl<'de> Visitor<'de> for TopTokenVisitor {
+    type Value = TopToken;
+
+    fn expecting(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        fmtr.write_str("a getTop entry")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<TopToken, A::Error> {
+        let mut fields = serde_json::Map::new();
+        while let Some((key, value)) = map.next_entry::<String, serde_json::Value>()? {
+            fields.insert(key, value);
+        }
+        let volume = take_ranking(&mut fields, "volume")?;
+        let volume_previous = take_ranking(&mut fields, "volumePrevious")?;
+        let cap = take_ranking(&mut fields, "cap")?;
+        let cap_previous = take_ranking(&mut fields, "capPrevious")?;
+        let unknown = match fields.remove("$unknown") {
+            Some(value) => UnknownFields::deserialize(value).map_err(de::Error::custom)?,
+            None => UnknownFields::default(),
+        };
+        let token_info =
+            TokenInfo::deserialize(serde_json::Value::Object(fields)).map_err(de::Error::custom)?;
+        Ok(TopToken {
+            token_info,
+            volume,
+            volume_previous,
+            cap,
+            cap_previous,
+            unknown,
+        })
+    }
+}
+
+// Ranking fields the criteria didn't ask for are left out of the entry
+fn take_ranking<E: de::Error>(
+    fields: &mut serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Result<f64, E> {
+    fields.remove(key).map_or(Ok(0.0), |value| {
+        flexible::deserialize(value).map_err(de::Error::custom)
+    })
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TopTotals {
+    #[serde(deserialize_with = "flexible::deserialize", default)]
+    pub tokens: u64,
+    #[serde(
+        rename = "tokensWithPrice",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
+    pub tokens_with_price: u64,
+    #[serde(deserialize_with = "flexible::deserialize", default)]
+    pub cap: f64,
+    #[serde(
+        rename = "capPrevious",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
+    pub cap_previous: f64,
+    #[serde(
+        rename = "volume24h",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
+    pub volume_24h: f64,
+    #[serde(
+        rename = "volumePrevious",
+        deserialize_with = "flexible::deserialize",
+        default
+    )]
+    pub volume_previous: f64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+// Response of `getTop`
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Top {
+    pub tokens: Vec<TopToken>,
+    #[serde(default)]
+    pub totals: TopTotals,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Price {
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub ts: u64,
-    #[serde(deserialize_with = "date_or_timestamp")]
+    #[serde(deserialize_with = "date_or_timestamp", serialize_with = "day_string")]
     pub date: Timestamp,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub open: f64,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub close: f64,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub high: f64,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub low: f64,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub volume: f64,
-    #[serde(rename(deserialize = "volumeConverted"))]
+    #[serde(rename = "volumeConverted", deserialize_with = "flexible::deserialize")]
     pub volume_usd: f64,
+    #[serde(deserialize_with = "flexible::deserialize")]
     pub average: f64,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct History {
-    #[serde(rename(deserialize = "countTxs"))]
+    #[serde(rename = "countTxs")]
     pub count_txs: Vec<CountTxs>,
     pub prices: Prices,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TokenDailyPriceHistory {
     pub history: History,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CreatedPool {
+    #[serde(rename = "poolId")]
+    pub pool_id: String,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+// Acknowledgement of a pool change, e.g. `{"deleted": true}` or `{"added": true}`
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PoolChange {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleared: Option<bool>,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+impl PoolChange {
+    #[must_use]
+    pub fn success(&self) -> bool {
+        [self.added, self.deleted, self.cleared].contains(&Some(true))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PoolAddresses {
+    pub addresses: Vec<Address>,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+// Recent operations of every pool address that had any
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub struct PoolLastOperations(HashMap<Address, Vec<Operations>>);
+
+impl Deref for PoolLastOperations {
+    type Target = HashMap<Address, Vec<Operations>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// Recent transactions of every pool address that had any
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub struct PoolLastTransactions(HashMap<Address, Vec<AddressTransaction>>);
+
+impl Deref for PoolLastTransactions {
+    type Target = HashMap<Address, Vec<AddressTransaction>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PoolAddressUpdate {
+    #[serde(default)]
+    pub transactions: Vec<AddressTransaction>,
+    #[serde(default)]
+    pub operations: Vec<Operations>,
+    #[serde(rename = "$unknown", default, skip_serializing)]
+    pub unknown: UnknownFields,
+}
+
+// Changes of every pool address updated within the requested period
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub struct PoolUpdates(HashMap<Address, PoolAddressUpdate>);
+
+impl Deref for PoolUpdates {
+    type Target = HashMap<Address, PoolAddressUpdate>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// Operation types accepted by the history routes and reported on `Operations`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OperationType {
+    Transfer,
+    Approve,
+    Issuance,
+    Burn,
+    Mint,
+    Redeem,
+    Unknown(String),
+}
+
+impl OperationType {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            OperationType::Transfer => "transfer",
+            OperationType::Approve => "approve",
+            OperationType::Issuance => "issuance",
+            OperationType::Burn => "burn",
+            OperationType::Mint => "mint",
+            OperationType::Redeem => "redeem",
+            OperationType::Unknown(value) => value,
+        }
+    }
+}
+
+impl Default for OperationType {
+    fn default() -> Self {
+        OperationType::Unknown(String::new())
+    }
+}
+
+impl From<&str> for OperationType {
+    fn from(value: &str) -> Self {
+        match value {
+            "transfer" => OperationType::Transfer,
+            "approve" => OperationType::Approve,
+            "issuance" => OperationType::Issuance,
+            "burn" => OperationType::Burn,
+            "mint" => OperationType::Mint,
+            "redeem" => OperationType::Redeem,
+            other => OperationType::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl Display for OperationType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(OperationType::from(value.as_str()))
+    }
+}
+
+impl Serialize for OperationType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+// Sort criteria for `getTop`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TopCriteria {
+    Trade,
+    Cap,
+    Count,
+    Unknown(String),
+}
+
+impl TopCriteria {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            TopCriteria::Trade => "trade",
+            TopCriteria::Cap => "cap",
+            TopCriteria::Count => "count",
+            TopCriteria::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<&str> for TopCriteria {
+    fn from(value: &str) -> Self {
+        match value {
+            "trade" => TopCriteria::Trade,
+            "cap" => TopCriteria::Cap,
+            "count" => TopCriteria::Count,
+            other => TopCriteria::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl Display for TopCriteria {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TopCriteria {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(TopCriteria::from(value.as_str()))
+    }
+}
+
+impl Serialize for TopCriteria {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
 }
 
 // Struct Params
-#[derive(Debug, Default)]
+// Serialized under the query parameter names. `extra_params` are appended as is
+// after the typed params, for options the crate doesn't model yet
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
 pub struct GetAddressInfoParams {
-    pub token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<Address>,
+    #[serde(rename = "showETHTotals")]
     pub show_eth_totals: bool,
+    // Adds `count_txs` to the response
+    #[serde(rename = "showTxsCount")]
+    pub show_txs_count: bool,
+    #[serde(rename = "extraParams", skip_serializing_if = "Vec::is_empty")]
+    pub extra_params: Vec<(String, String)>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
 pub struct GetTokenHistoryParams {
-    pub history_type: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub history_type: Option<OperationType>,
     pub limit: u64,
     pub timestamp: Timestamp,
+    #[serde(rename = "extraParams", skip_serializing_if = "Vec::is_empty")]
+    pub extra_params: Vec<(String, String)>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
 pub struct GetAddressHistoryParams {
-    pub history_type: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub history_type: Option<OperationType>,
     pub limit: u64,
     pub timestamp: Timestamp,
-    pub token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<Address>,
+    #[serde(rename = "extraParams", skip_serializing_if = "Vec::is_empty")]
+    pub extra_params: Vec<(String, String)>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
 pub struct GetAddressTransactionsParams {
     pub limit: u64,
     pub timestamp: Timestamp,
+    #[serde(rename = "showZeroValues")]
     pub show_zero_values: bool,
+    #[serde(rename = "extraParams", skip_serializing_if = "Vec::is_empty")]
+    pub extra_params: Vec<(String, String)>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
 pub struct GetTopParams {
     pub limit: u64,
-    pub criteria: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub criteria: Option<TopCriteria>,
+    #[serde(rename = "extraParams", skip_serializing_if = "Vec::is_empty")]
+    pub extra_params: Vec<(String, String)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CreatePoolParams {
+    // Addresses to watch from the start; the pool may also be created empty
+    pub addresses: Vec<Address>,
+}
+
+// Shared by `addPoolAddresses` and `deletePoolAddresses`
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PoolAddressesParams {
+    #[serde(rename = "poolId")]
+    pub pool_id: String,
+    pub addresses: Vec<Address>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    const TOKEN: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+    const HOLDER: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+    const HASH: &str = "0x1c0e8a3cd8b8d22a8ee0bbc1e5e7c3e10b3df0ff2b4e6bc1cc0a3a1e1d9b2f3a";
+
+    // Every field of `expected` must come back with the same name and encoding
+    fn assert_contains(actual: &Value, expected: &Value, path: &str) {
+        match (actual, expected) {
+            (Value::Object(actual), Value::Object(expected)) => {
+                for (key, value) in expected {
+                    let path = format!("{path}.{key}");
+                    let found = actual.get(key);
+                    assert!(found.is_some(), "missing {}", path);
+                    assert_contains(found.unwrap(), value, &path);
+                }
+            }
+            (Value::Array(actual), Value::Array(expected)) => {
+                assert_eq!(actual.len(), expected.len(), "length of {path}");
+                for (index, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+                    assert_contains(actual, expected, &format!("{path}[{index}]"));
+                }
+            }
+            _ => assert_eq!(actual, expected, "value of {path}"),
+        }
+    }
+
+    fn assert_round_trip<T: DeserializeOwned + Serialize>(original: &Value) {
+        let parsed: T = serde_json::from_value(original.clone()).unwrap();
+        let written = serde_json::to_value(&parsed).unwrap();
+        assert_contains(&written, original, "$");
+
+        let reparsed: T = serde_json::from_value(written.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&reparsed).unwrap(), written);
+    }
+
+    fn price() -> Value {
+        json!({
+            "rate": 1.0012,
+            "currency": "USD",
+            "diff": -0.01,
+            "diff7d": 0.02,
+            "diff30d": 0.05,
+            "volume24h": 45_000_000_000.5,
+            "volDiff1": 1.5,
+            "volDiff7": -2.5,
+            "volDiff30": 3.25,
+            "marketCapUsd": 68_000_000_000.25,
+            "availableSupply": 68_000_000_000.75,
+            "ts": 1_633_046_400
+        })
+    }
+
+    fn token_info() -> Value {
+        json!({
+            "address": TOKEN,
+            "name": "Tether USD",
+            "decimals": "6",
+            "symbol": "USDT",
+            "totalSupply": "39823315849942740",
+            "owner": HOLDER,
+            "txsCount": 1200,
+            "transfersCount": 1_000_000,
+            "lastUpdated": 1_633_046_400,
+            "issuancesCount": 3,
+            "holdersCount": 4_500_000,
+            "website": "https://tether.to",
+            "price": price(),
+            "countOps": 12,
+            "publicTags": ["Stablecoins"],
+            "added": 1_500_000_000
+        })
+    }
+
+    fn operation() -> Value {
+        json!({
+            "timestamp": 1_633_046_400,
+            "transactionHash": HASH,
+            "tokenInfo": token_info(),
+            "type": "transfer",
+            "from": HOLDER,
+            "to": TOKEN,
+            "value": "1000000"
+        })
+    }
+
+    fn transaction() -> Value {
+        json!({
+            "timestamp": 1_633_046_400,
+            "from": HOLDER,
+            "to": TOKEN,
+            "hash": HASH,
+            "value": 0.25,
+            "input": "0x",
+            "success": true
+        })
+    }
+
+    #[test]
+    fn token_types_round_trip() {
+        assert_round_trip::<LastBlock>(&json!({"lastBlock": 13_330_000}));
+        assert_round_trip::<TokenPrice>(&price());
+        assert_round_trip::<TokenInfo>(&token_info());
+        assert_round_trip::<TopTokenHolders>(&json!({
+            "holders": [{"address": HOLDER, "balance": "5000000", "share": 50.5}]
+        }));
+        assert_round_trip::<TokenHistory>(&json!({ "operations": [operation()] }));
+        assert_round_trip::<TopTokens>(&json!({"tokens": [token_info()], "opCount": 7}));
+        assert_round_trip::<TokensNew>(&json!([token_info()]));
+    }
+
+    #[test]
+    fn pricing_tells_unpriced_from_zero() {
+        let unpriced: Pricing = serde_json::from_value(json!(false)).unwrap();
+        assert_eq!(unpriced, Pricing::Unpriced);
+        assert_eq!(unpriced.rate(), None);
+
+        let zero: Pricing = serde_json::from_value(json!({"rate": 0.0})).unwrap();
+        assert!(zero.is_priced());
+        assert_eq!(zero.rate(), Some(0.0));
+
+        let malformed: Pricing = serde_json::from_value(json!({"rate": "n/a"})).unwrap();
+        assert_eq!(malformed, Pricing::Malformed(json!({"rate": "n/a"})));
+        assert_eq!(
+            serde_json::from_value::<Pricing>(json!(1.5)).unwrap(),
+            Pricing::Malformed(json!(1.5))
+        );
+        for rateless in [json!({}), json!({"currency": "USD"})] {
+            let pricing: Pricing = serde_json::from_value(rateless.clone()).unwrap();
+            assert_eq!(pricing, Pricing::Malformed(rateless));
+            assert_eq!(pricing.rate(), None);
+        }
+
+        let mut info = token_info();
+        info["price"] = json!({"rate": "n/a"});
+        assert_round_trip::<TokenInfo>(&info);
+        info["price"] = json!(false);
+        assert_round_trip::<TokenInfo>(&info);
+        info.as_object_mut().unwrap().remove("price");
+        let info: TokenInfo = serde_json::from_value(info).unwrap();
+        assert!(!info.price.is_priced());
+    }
+
+    #[test]
+    fn address_types_round_trip() {
+        let token = json!({
+            "tokenInfo": token_info(),
+            "balance": "2500000",
+            "rawBalance": "2500000",
+            "totalIn": "3000000",
+            "totalOut": "500000"
+        });
+        assert_round_trip::<AddressInfo>(&json!({
+            "address": HOLDER,
+            "ETH": {
+                "price": price(),
+                "balance": 1.5,
+                "rawBalance": "1500000000000000000",
+                "totalIn": 2.5,
+                "totalOut": 1.0
+            },
+            "contractInfo": {
+                "creatorAddress": HOLDER,
+                "transactionHash": HASH,
+                "timestamp": 1_511_829_681
+            },
+            "tokenInfo": token_info(),
+            "tokens": [token],
+            "countTxs": 42
+        }));
+        assert_round_trip::<AddressTransactions>(&json!([transaction()]));
+        assert_round_trip::<TxInfo>(&json!({
+            "hash": HASH,
+            "timestamp": 1_633_046_400,
+            "blockNumber": 13_330_000,
+            "confirmations": 120,
+            "success": true,
+            "from": HOLDER,
+            "to": TOKEN,
+            "value": 0.5,
+            "input": "0xa9059cbb",
+            "gasLimit": "80000",
+            "gasUsed": "51379",
+            "gasPrice": "123456789012345678901",
+            "logs": [{"address": TOKEN, "topics": ["0xddf2"], "data": "0x01"}],
+            "operations": [operation()]
+        }));
+    }
+
+    #[test]
+    fn round_trip_normalises_decimals_and_keeps_eth_digits() {
+        let mut numeric = token_info();
+        numeric["decimals"] = json!(6);
+        let info: TokenInfo = serde_json::from_value(numeric).unwrap();
+        assert_eq!(serde_json::to_value(&info).unwrap()["decimals"], json!("6"));
+
+        let eth: ETH = serde_json::from_value(json!({
+            "price": false,
+            "balance": "1234567.123456789012345678",
+            "rawBalance": "1234567123456789012345678"
+        }))
+        .unwrap();
+        let written = serde_json::to_string(&eth).unwrap();
+        assert!(
+            written.contains(r#""balance":"1234567.123456789012345678""#),
+            "{}",
+            written
+        );
+        assert!(written.contains(r#""totalIn":0.0"#), "{}", written);
+        let reparsed: ETH = serde_json::from_str(&written).unwrap();
+        assert_eq!(reparsed.balance, eth.balance);
+    }
+
+    #[test]
+    fn history_types_round_trip() {
+        let count =
+            json!({"_id": {"year": 2021, "month": 10, "day": 1}, "ts": 1_633_046_400, "cnt": 7});
+        assert_round_trip::<TokenDailyTransactionCounts>(&json!({ "countTxs": [count] }));
+        assert_round_trip::<TokenDailyPriceHistory>(&json!({
+            "history": {
+                "countTxs": [count],
+                "prices": [{
+                    "ts": 1_633_046_400,
+                    "date": "2021-10-01",
+                    "open": 1.001,
+                    "close": 1.002,
+                    "high": 1.01,
+                    "low": 0.99,
+                    "volume": 1000.5,
+                    "volumeConverted": 1001.5,
+                    "average": 1.0005
+                }]
+            }
+        }));
+        assert_round_trip::<Top>(&json!({
+            "tokens": [{
+                "address": TOKEN,
+                "name": "Tether USD",
+                "decimals": "6",
+                "symbol": "USDT",
+                "volume": 1500.5,
+                "volumePrevious": 1200.25,
+                "cap": 75_000_000_000.5,
+                "capPrevious": 74_000_000_000.5
+            }],
+            "totals": {
+                "tokens": 2500,
+                "tokensWithPrice": 900,
+                "cap": 1.5e12,
+                "capPrevious": 1.4e12,
+                "volume24h": 1.5e9,
+                "volumePrevious": 1.25e9
+            }
+        }));
+    }
+
+    #[test]
+    fn pool_types_round_trip() {
+        assert_round_trip::<CreatedPool>(&json!({"poolId": "pool-1"}));
+        assert_round_trip::<PoolChange>(&json!({"deleted": true}));
+        assert_round_trip::<PoolAddresses>(&json!({ "addresses": [TOKEN, HOLDER] }));
+        assert_round_trip::<PoolLastOperations>(&json!({ HOLDER: [operation()] }));
+        assert_round_trip::<PoolLastTransactions>(&json!({ HOLDER: [transaction()] }));
+        assert_round_trip::<PoolUpdates>(&json!({
+            HOLDER: {"transactions": [transaction()], "operations": [operation()]}
+        }));
+    }
+
+    #[test]
+    fn params_round_trip() {
+        assert_round_trip::<GetAddressInfoParams>(&json!({
+            "token": TOKEN,
+            "showETHTotals": true,
+            "showTxsCount": false,
+            "extraParams": [["showTokens", "false"]]
+        }));
+        assert_round_trip::<GetTokenHistoryParams>(&json!({
+            "type": "transfer",
+            "limit": 50,
+            "timestamp": 1_633_046_400
+        }));
+        assert_round_trip::<GetAddressHistoryParams>(&json!({
+            "type": "approve",
+            "limit": 10,
+            "timestamp": 0,
+            "token": TOKEN
+        }));
+        assert_round_trip::<GetAddressTransactionsParams>(&json!({
+            "limit": 10,
+            "timestamp": 1_633_046_400,
+            "showZeroValues": true
+        }));
+        assert_round_trip::<GetTopParams>(&json!({"limit": 20, "criteria": "cap"}));
+        assert_round_trip::<CreatePoolParams>(&json!({ "addresses": [TOKEN] }));
+        assert_round_trip::<PoolAddressesParams>(
+            &json!({"poolId": "pool-1", "addresses": [HOLDER]}),
+        );
+    }
+
+    #[test]
+    fn numbers_parse_from_either_encoding() {
+        let info: TokenInfo = serde_json::from_value(json!({
+            "address": TOKEN, "name": "Tether", "decimals": 6, "symbol": "USDT",
+            "holdersCount": "4500000", "price": false,
+        }))
+        .unwrap();
+        assert_eq!(info.decimals, 6);
+        assert_eq!(info.holders_count, 4_500_000);
+
+        let token: Token = serde_json::from_value(json!({
+            "tokenInfo": {"address": TOKEN, "name": "Tether", "decimals": "6", "symbol": "USDT"},
+            "balance": "1.5e6", "totalIn": "", "totalOut": 0,
+        }))
+        .unwrap();
+        assert_eq!(token.token_info.decimals, 6);
+        assert_eq!(token.balance, RawAmount::from(1_500_000));
+        assert_eq!(token.total_in, RawAmount::zero());
+
+        let holders: TopTokenHolders = serde_json::from_value(json!({
+            "holders": [{"address": HOLDER, "balance": 10, "share": "12.5"}],
+        }))
+        .unwrap();
+        assert!((holders.holders[0].share - 12.5).abs() < f64::EPSILON);
+
+        let holders: TopTokenHolders = serde_json::from_str(&format!(
+            r#"{{"holders": [{{"address": "{HOLDER}", "balance": 1.0e+26, "share": 1}}]}}"#
+        ))
+        .unwrap();
+        assert_eq!(
+            holders.holders[0].balance.to_string(),
+            "100000000000000000000000000"
+        );
+
+        let err = serde_json::from_value::<LastBlock>(json!({"lastBlock": "latest"})).unwrap_err();
+        assert!(err.to_string().contains(r#"string "latest""#));
+    }
+
+    #[test]
+    fn drift_reaches_nested_types() {
+        use crate::drift::{from_slice_keeping_unknown, DriftReport};
+
+        let body = json!({
+            "address": HOLDER,
+            "ETH": {"balance": 1.5, "rawBalance": "1500000000000000000", "price": false},
+            "tokens": [{
+                "tokenInfo": {
+                    "address": TOKEN, "name": "Tether", "decimals": "6", "symbol": "USDT",
+                    "bridged": true,
+                },
+                "balance": 10, "totalIn": 0, "totalOut": 0,
+            }],
+        })
+        .to_string();
+
+        let info: AddressInfo = from_slice_keeping_unknown(body.as_bytes()).unwrap();
+        assert_eq!(
+            info.tokens[0].token_info.unknown.get("bridged"),
+            Some(&json!(true))
+        );
+        assert!(info.unknown.is_empty());
+
+        let mut report = DriftReport::new();
+        report.record::<AddressInfo>(body.as_bytes()).unwrap();
+        let token_info = report.get("TokenInfo").unwrap();
+        assert_eq!(token_info.new_fields.get("bridged"), Some(&1));
+        assert_eq!(token_info.missing_fields.get("owner"), Some(&1));
+        assert!(!token_info.missing_fields.contains_key("$unknown"));
+        let token = report.get("Token").unwrap();
+        assert_eq!(token.missing_fields.get("rawBalance"), Some(&1));
+        assert!(report.get("ETH").unwrap().new_fields.is_empty());
+    }
+
+    #[test]
+    fn drift_reaches_prices_and_top_tokens() {
+        use crate::drift::{from_slice_keeping_unknown, DriftReport};
+
+        let priced = json!({
+            "address": TOKEN, "name": "Tether", "decimals": "6", "symbol": "USDT",
+            "price": {"rate": 1.0, "currency": "USD", "ts": 1_633_046_400, "tsMs": 1},
+        })
+        .to_string();
+        let info: TokenInfo = from_slice_keeping_unknown(priced.as_bytes()).unwrap();
+        assert_eq!(
+            info.price.price().unwrap().unknown.get("tsMs"),
+            Some(&json!(1))
+        );
+        let plain: TokenInfo = serde_json::from_str(&priced).unwrap();
+        assert!(plain.price.price().unwrap().unknown.is_empty());
+
+        let mut report = DriftReport::new();
+        report.record::<TokenInfo>(priced.as_bytes()).unwrap();
+        let price = report.get("TokenPrice").unwrap();
+        assert_eq!(price.new_fields.get("tsMs"), Some(&1));
+        assert_eq!(price.missing_fields.get("diff"), Some(&1));
+        assert!(!price.missing_fields.contains_key("rate"));
+
+        let top = json!({
+            "tokens": [{
+                "address": TOKEN, "name": "Tether", "decimals": 6, "symbol": "USDT",
+                "cap": "1000.5", "capPrevious": 990, "rank": 3, "price": false,
+            }],
+        })
+        .to_string();
+        let tokens: Top = from_slice_keeping_unknown(top.as_bytes()).unwrap();
+        let token = &tokens.tokens[0];
+        assert_eq!(token.unknown.get("rank"), Some(&json!(3)));
+        assert!(token.token_info.unknown.is_empty());
+        assert_eq!(token.token_info.decimals, 6);
+        assert!((token.cap - 1000.5).abs() < f64::EPSILON);
+
+        report.record::<Top>(top.as_bytes()).unwrap();
+        let top_token = report.get("TopToken").unwrap();
+        assert_eq!(top_token.new_fields.get("rank"), Some(&1));
+        assert_eq!(top_token.missing_fields.get("volume"), Some(&1));
+        assert!(!top_token.missing_fields.contains_key("cap"));
+        assert_eq!(report.get("TokenInfo").unwrap().seen, 1);
+    }
+
+    #[test]
+    fn top_token_fields_cover_token_info() {
+        let written = serde_json::to_value(TokenInfo::default()).unwrap();
+        for key in written.as_object().unwrap().keys() {
+            assert!(TOP_TOKEN_FIELDS.contains(&key.as_str()), "{}", key);
+        }
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Stamped {
+        #[serde(deserialize_with = "date_or_timestamp")]
+        at: Timestamp,
+    }
+
+    #[test]
+    fn timestamps_warn_when_lenient() {
+        use crate::strictness::{Parsed, Strictness};
+
+        let body = br#"[{"at": 1700000000}, {"at": "2023-11-14"}, {"at": "soon"}, {"at": 18446744073709551615}]"#;
+        let parsed: Parsed<Vec<Stamped>> = Parsed::from_slice(body, Strictness::Lenient).unwrap();
+        assert_eq!(parsed[0].at.timestamp(), 1_700_000_000);
+        assert_eq!(parsed[1].at.timestamp(), 1_699_920_000);
+        assert_eq!(parsed[2].at, Timestamp::default());
+        assert_eq!(parsed[3].at, Timestamp::default());
+        let warnings: Vec<String> = parsed.warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                r#"unrecognised date "soon""#,
+                r#"timestamp out of range "18446744073709551615""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn timestamps_fail_when_strict() {
+        use crate::strictness::{Parsed, Strictness};
+
+        let err =
+            Parsed::<Stamped>::from_slice(br#"{"at": "soon"}"#, Strictness::Strict).unwrap_err();
+        assert!(err.to_string().contains(r#"unrecognised date "soon""#));
+        let err =
+            Parsed::<Stamped>::from_slice(br#"{"at": 9223372036854775807}"#, Strictness::Strict)
+                .unwrap_err();
+        assert!(err.to_string().contains("timestamp out of range"));
+        assert!(Parsed::<Stamped>::from_slice(br#"{"at": 0}"#, Strictness::Strict).is_ok());
+    }
 }